@@ -0,0 +1,124 @@
+use std::{fs, path::PathBuf};
+
+use utils::{
+    timing::{change_percent, Baseline},
+    DEFAULT_YEAR,
+};
+
+use crate::{
+    days::{days_of, parse_year},
+    run::parse_days,
+};
+
+const DEFAULT_ITERATIONS: u32 = 20;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// `aoc bench <DAYS> [--year <YEAR>] [--iterations <N>] [--baseline <FILE>] [--save <FILE>] [--threshold <PCT>]`
+///
+/// Times parse, part 1 and part 2 of each selected day of the year separately. With
+/// `--baseline` each median is compared to the saved one, and anything slower by
+/// more than the threshold is flagged. `--save` writes this run's medians,
+/// keeping whatever the file already had for days that weren't run.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut spec = None;
+    let mut year = DEFAULT_YEAR;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut baseline_path = None;
+    let mut save_path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = parse_year(args.next())?,
+            "-n" | "--iterations" => {
+                let n = args.next().and_then(|n| n.parse::<u32>().ok());
+                iterations = n.filter(|n| *n > 0).ok_or("--iterations needs a count")?;
+            }
+            "--threshold" => {
+                let pct = args.next().and_then(|p| p.parse::<f64>().ok());
+                threshold = pct.ok_or("--threshold needs a percentage")?;
+            }
+            "--baseline" => {
+                baseline_path = Some(PathBuf::from(args.next().ok_or("--baseline needs a file")?))
+            }
+            "--save" => save_path = Some(PathBuf::from(args.next().ok_or("--save needs a file")?)),
+            s if spec.is_none() => spec = Some(s.to_string()),
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+    let days = parse_days(
+        spec.as_deref()
+            .ok_or("bench needs a day, a range or 'all'")?,
+        year,
+    )?;
+    let baseline = match &baseline_path {
+        Some(path) => Some(read_baseline(path)?),
+        None => None,
+    };
+
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>10}{}",
+        "Day",
+        "Phase",
+        "min",
+        "median",
+        "mean",
+        "stddev",
+        if baseline.is_some() {
+            "  vs baseline"
+        } else {
+            ""
+        }
+    );
+    let mut timings = vec![];
+    let mut regressions = 0;
+    for registered in days_of(year).filter(|r| days.contains(&r.day)) {
+        let day_timings = match registered.time_puzzle(iterations) {
+            Ok(day_timings) => day_timings,
+            Err(e) => {
+                println!("{:>3}  {e}", registered.day);
+                continue;
+            }
+        };
+        for (phase, stats) in day_timings.stats() {
+            let mut row = format!(
+                "{:>3}  {:<5}  {:>10.2?}  {:>10.2?}  {:>10.2?}  {:>10.2?}",
+                day_timings.day, phase, stats.min, stats.median, stats.mean, stats.stddev
+            );
+            if let Some(then) = baseline
+                .as_ref()
+                .and_then(|b| b.get(day_timings.year, day_timings.day, phase))
+            {
+                let change = change_percent(then, stats.median);
+                row.push_str(&format!("  {change:>+7.1}%"));
+                if change > threshold {
+                    row.push_str("  REGRESSION");
+                    regressions += 1;
+                }
+            }
+            println!("{row}");
+        }
+        timings.push(day_timings);
+    }
+
+    if let Some(path) = &save_path {
+        // Keep the entries for days that weren't part of this run
+        let mut saved = if path.exists() {
+            read_baseline(path)?
+        } else {
+            Baseline::default()
+        };
+        saved.merge(Baseline::from_timings(&timings));
+        fs::write(path, saved.to_string()).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!("saved baseline to {}", path.display());
+    }
+    if regressions > 0 {
+        println!("{regressions} phase(s) more than {threshold}% slower than the baseline");
+    }
+    Ok(())
+}
+
+fn read_baseline(path: &PathBuf) -> Result<Baseline, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    Baseline::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+}
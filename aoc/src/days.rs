@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+
+use utils::{runner::Registered, DEFAULT_YEAR};
+
+/// Every day the runner knows about, ordered by year, then day
+pub const DAYS: &[Registered] = &[
+    Registered::of::<day1::Day1>(),
+    Registered::of::<day2::Day2>(),
+    Registered::of::<day3::Day3>(),
+    Registered::of::<day4::Day4>(),
+];
+
+/// The registered days of one year
+pub fn days_of(year: u32) -> impl Iterator<Item = &'static Registered> {
+    DAYS.iter().filter(move |r| r.year == year)
+}
+
+/// Name of the crate, and of its directory, holding a day's solution
+///
+/// The default year keeps the plain `day{N}`; other years get `y{year}_day{N}`,
+/// since a crate name can't start with a digit.
+pub fn crate_name(year: u32, day: u32) -> String {
+    if year == DEFAULT_YEAR {
+        format!("day{day}")
+    } else {
+        format!("y{year}_day{day}")
+    }
+}
+
+/// The workspace root holding the day crates
+///
+/// Taken from where the runner was built rather than from the puzzle directory,
+/// which `AOC_PUZZLE_DIR` can move anywhere.
+pub fn workspace_dir() -> PathBuf {
+    let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+    runner.parent().unwrap_or(runner).to_path_buf()
+}
+
+/// Reads the value given to `--year`
+pub fn parse_year(value: Option<&String>) -> Result<u32, String> {
+    value
+        .and_then(|y| y.parse::<u32>().ok())
+        .filter(|y| *y >= 2015)
+        .ok_or("--year needs a year, 2015 or later".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::{crate_name, days_of, parse_year, workspace_dir};
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(crate_name(2023, 4), "day4");
+        assert_eq!(crate_name(2022, 12), "y2022_day12");
+        assert_eq!(days_of(2023).count(), 4);
+        assert_eq!(days_of(1999).count(), 0);
+        assert_eq!(parse_year(Some(&"2022".to_string())), Ok(2022));
+        assert!(parse_year(Some(&"22".to_string())).is_err());
+        assert!(parse_year(None).is_err());
+        assert!(workspace_dir().join("aoc/src/days.rs").is_file());
+    }
+}
@@ -0,0 +1,85 @@
+use std::{fs, path::PathBuf};
+
+use utils::examples::{example_file_name, manifest_file_name, PuzzlePage};
+
+use crate::days::parse_year;
+
+/// `aoc examples <PAGE.html> [--year <YEAR>] [--day <N>] [--force]`
+///
+/// Writes every example block to `puzzles/{year}/day{N}_test{K}.txt` and the
+/// answers found for them to `puzzles/{year}/day{N}_tests.toml`. The year and day
+/// are read from the page unless given.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut page_path = None;
+    let mut year = None;
+    let mut day = None;
+    let mut force = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = Some(parse_year(args.next())?),
+            "--day" => {
+                let n = args.next().and_then(|n| n.parse::<u32>().ok());
+                day = Some(n.ok_or("--day needs a number")?);
+            }
+            "--force" => force = true,
+            path if page_path.is_none() => page_path = Some(PathBuf::from(path)),
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+    let page_path = page_path.ok_or("examples needs a saved puzzle page")?;
+
+    let html =
+        fs::read_to_string(&page_path).map_err(|e| format!("{}: {e}", page_path.display()))?;
+    let page = PuzzlePage::parse(&html);
+    let day = day
+        .or(page.day)
+        .ok_or("couldn't find the day on the page, pass --day")?;
+    let year = year
+        .or(page.year)
+        .ok_or("couldn't find the year on the page, pass --year")?;
+    if page.examples.is_empty() {
+        return Err(format!("no <pre><code> blocks in {}", page_path.display()));
+    }
+
+    let puzzle_dir = utils::year_dir(year);
+    let mut outputs: Vec<(PathBuf, String)> = page
+        .examples
+        .iter()
+        .enumerate()
+        .map(|(ndx, example)| {
+            let name = example_file_name(day, ndx as u32 + 1);
+            (puzzle_dir.join(name), example.text.clone())
+        })
+        .collect();
+    outputs.push((puzzle_dir.join(manifest_file_name(day)), page.manifest(day)));
+
+    if !force {
+        let existing: Vec<String> = outputs
+            .iter()
+            .filter(|(path, _)| path.exists())
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            return Err(format!(
+                "refusing to overwrite, pass --force:\n  {}",
+                existing.join("\n  ")
+            ));
+        }
+    }
+
+    fs::create_dir_all(&puzzle_dir).map_err(|e| format!("{}: {e}", puzzle_dir.display()))?;
+    for (path, contents) in &outputs {
+        fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!("wrote {}", path.display());
+    }
+    for answer in &page.answers {
+        println!(
+            "part {} example {}: {}",
+            answer.part,
+            answer.example + 1,
+            answer.answer
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::{env, process};
+
+mod bench;
+mod days;
+mod examples;
+mod new;
+mod run;
+mod submit;
+mod verify;
+mod watch;
+
+const USAGE: &str = "Usage: aoc <COMMAND>
+
+Commands:
+  bench <DAYS> [--year <YEAR>] [--iterations <N>] [--baseline <FILE>] [--save <FILE>] [--threshold <PCT>]
+      Time parse, part 1 and part 2 separately and compare against a saved baseline
+  examples <PAGE.html> [--year <YEAR>] [--day <N>] [--force]
+      Extract the example blocks and answers from a saved puzzle page
+  new [DAY] [--year <YEAR>]
+      Create the next (or the given) day's crate and puzzle files from the templates
+  run <DAY | FIRST..END | FIRST..=LAST | all> [--year <YEAR>] [--part <N>] [--format <text|json|csv>]
+      [--isolate] [--timeout <SECS>] [--jobs <N>]
+      Solve the given days against their puzzle inputs, with --isolate catching
+      panics and timeouts per part and --jobs running days in parallel
+  submit <DAY> <PART> [ANSWER] [--year <YEAR>]
+      Send an answer, by default the day's own, unless earlier guesses rule it out
+  verify [DAYS] [--year <YEAR>] [--record]
+      Check every answer against the confirmed ones in puzzles/<YEAR>/answers
+  watch <DAY> [--year <YEAR>] [--interval <MS>]
+      Rebuild and re-run the examples and the input whenever the day changes
+
+The year defaults to 2023.";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("bench") => bench::run(&args[1..]),
+        Some("examples") => examples::run(&args[1..]),
+        Some("new") => new::run(&args[1..]),
+        Some("run") => run::run(&args[1..]),
+        Some("submit") => submit::run(&args[1..]),
+        Some("verify") => verify::run(&args[1..]),
+        Some("watch") => watch::run(&args[1..]),
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            return;
+        }
+        Some(other) => Err(format!("unknown command '{other}'\n{USAGE}")),
+        None => Err(USAGE.to_string()),
+    };
+    if let Err(msg) = result {
+        eprintln!("aoc: {msg}");
+        process::exit(1);
+    }
+}
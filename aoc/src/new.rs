@@ -0,0 +1,243 @@
+use std::{fs, path::Path};
+
+use utils::{
+    examples::{example_file_name, manifest_file_name},
+    DEFAULT_YEAR,
+};
+
+use crate::days::{crate_name, days_of, parse_year, workspace_dir};
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_TEMPLATE: &str = include_str!("../templates/main.rs.tmpl");
+const TESTS_TEMPLATE: &str = include_str!("../templates/tests.toml.tmpl");
+
+/// `aoc new [DAY] [--year <YEAR>]`
+///
+/// Creates the day's crate from the templates (the day after the last one
+/// registered for the year by default), makes sure the workspace includes it,
+/// registers it with the runner, and creates the day's puzzle, example and
+/// manifest files in `puzzles/{year}/`. Refuses to touch a day that already
+/// exists; puzzle files that are already there, say from `aoc examples`, are left
+/// alone.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut year = DEFAULT_YEAR;
+    let mut day = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = parse_year(args.next())?,
+            d if day.is_none() => {
+                day = Some(d.parse::<u32>().map_err(|_| format!("'{d}' isn't a day"))?)
+            }
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+    let day = day.unwrap_or_else(|| days_of(year).map(|r| r.day).max().unwrap_or(0) + 1);
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {day}"));
+    }
+
+    let workspace = workspace_dir();
+    let puzzle_dir = utils::year_dir(year);
+    let name = crate_name(year, day);
+    let crate_dir = workspace.join(&name);
+    if crate_dir.exists() || days_of(year).any(|r| r.day == day) {
+        return Err(format!("{name} already exists"));
+    }
+
+    // Work out every edit before writing anything
+    let read =
+        |path: &Path| fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()));
+    let workspace_toml = workspace.join("Cargo.toml");
+    let runner_toml = workspace.join("aoc/Cargo.toml");
+    let days_rs = workspace.join("aoc/src/days.rs");
+    let mut edits = vec![
+        (
+            runner_toml.clone(),
+            add_dependency(&read(&runner_toml)?, &name),
+        ),
+        (days_rs.clone(), register_day(&read(&days_rs)?, year, day)?),
+    ];
+    if let Some(members) = add_member(&read(&workspace_toml)?, &name)? {
+        edits.push((workspace_toml, members));
+    }
+
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!("wrote {}", path.display());
+        Ok::<(), String>(())
+    };
+    fs::create_dir_all(crate_dir.join("src"))
+        .map_err(|e| format!("{}: {e}", crate_dir.display()))?;
+    let render = |template| render(template, year, day);
+    write(&crate_dir.join("Cargo.toml"), &render(CARGO_TEMPLATE))?;
+    write(&crate_dir.join("src/lib.rs"), &render(LIB_TEMPLATE))?;
+    write(&crate_dir.join("src/main.rs"), &render(MAIN_TEMPLATE))?;
+    for (path, contents) in &edits {
+        write(path, contents)?;
+    }
+
+    fs::create_dir_all(&puzzle_dir).map_err(|e| format!("{}: {e}", puzzle_dir.display()))?;
+    let puzzle_files = [
+        (format!("day{day}.txt"), String::new()),
+        (example_file_name(day, 1), String::new()),
+        (example_file_name(day, 2), String::new()),
+        (manifest_file_name(day), render(TESTS_TEMPLATE)),
+    ];
+    for (file_name, contents) in puzzle_files {
+        let path = puzzle_dir.join(file_name);
+        if path.exists() {
+            println!("kept {}", path.display());
+        } else {
+            write(&path, &contents)?;
+        }
+    }
+    Ok(())
+}
+
+fn render(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{{CRATE}}", &crate_name(year, day))
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY}}", &day.to_string())
+}
+
+/// Adds `{name} = { path = "../{name}" }` after the runner's last path dependency
+fn add_dependency(cargo_toml: &str, name: &str) -> String {
+    let line = format!("{name} = {{ path = \"../{name}\" }}");
+    let mut lines: Vec<&str> = cargo_toml.lines().collect();
+    let at = lines
+        .iter()
+        .rposition(|l| l.contains("path = \"../"))
+        .map_or(lines.len(), |ndx| ndx + 1);
+    lines.insert(at, &line);
+    lines.join("\n") + "\n"
+}
+
+/// Adds the day to the runner's `DAYS`, keeping them ordered by (year, day)
+fn register_day(days_rs: &str, year: u32, day: u32) -> Result<String, String> {
+    let entry = format!(
+        "    Registered::of::<{}::Day{day}>(),",
+        crate_name(year, day)
+    );
+    let mut lines: Vec<&str> = days_rs.lines().collect();
+    let end = lines
+        .iter()
+        .position(|l| l.trim() == "];")
+        .ok_or("couldn't find the end of DAYS in aoc/src/days.rs")?;
+    // The inverse of `crate_name`
+    let registered = |line: &str| -> Option<(u32, u32)> {
+        let name = line
+            .trim()
+            .strip_prefix("Registered::of::<")?
+            .split("::")
+            .next()?;
+        let (year, day) = match name.strip_prefix('y') {
+            Some(rest) => {
+                let (year, day) = rest.split_once("_day")?;
+                (year.parse().ok()?, day)
+            }
+            None => (DEFAULT_YEAR, name.strip_prefix("day")?),
+        };
+        Some((year, day.parse().ok()?))
+    };
+    let at = lines[..end]
+        .iter()
+        .position(|l| registered(l).is_some_and(|r| r > (year, day)))
+        .unwrap_or(end);
+    lines.insert(at, &entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the crate to the workspace members, `None` if they already cover it
+fn add_member(cargo_toml: &str, name: &str) -> Result<Option<String>, String> {
+    let table: toml::Table = cargo_toml.parse().map_err(|e| format!("Cargo.toml: {e}"))?;
+    let members = table
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(toml::Value::as_array)
+        .ok_or("Cargo.toml has no workspace members")?;
+    let covered = members.iter().filter_map(toml::Value::as_str).any(|m| {
+        m == name
+            || m.strip_suffix('*')
+                .is_some_and(|prefix| name.starts_with(prefix))
+    });
+    if covered {
+        return Ok(None);
+    }
+    let start = cargo_toml
+        .find("members")
+        .and_then(|at| cargo_toml[at..].find(']').map(|end| at + end))
+        .ok_or("couldn't find the end of the workspace members")?;
+    Ok(Some(format!(
+        "{}, \"{name}\"{}",
+        &cargo_toml[..start],
+        &cargo_toml[start..]
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::new::{
+        add_dependency, add_member, register_day, render, LIB_TEMPLATE, MAIN_TEMPLATE,
+    };
+
+    const DAYS_RS: &str = "use utils::runner::Registered;
+
+pub const DAYS: &[Registered] = &[
+    Registered::of::<day1::Day1>(),
+    Registered::of::<day12::Day12>(),
+];
+";
+
+    #[test]
+    fn test_register_day() {
+        let days = register_day(DAYS_RS, 2023, 5).unwrap();
+        assert!(days.contains(
+            "Day1>(),\n    Registered::of::<day5::Day5>(),\n    Registered::of::<day12::"
+        ));
+        let days = register_day(&days, 2023, 13).unwrap();
+        assert!(days.ends_with("Day12>(),\n    Registered::of::<day13::Day13>(),\n];\n"));
+        // Earlier seasons come first, later ones last
+        let days = register_day(&days, 2022, 25).unwrap();
+        assert!(days.contains(
+            "[\n    Registered::of::<y2022_day25::Day25>(),\n    Registered::of::<day1::"
+        ));
+        let days = register_day(&days, 2024, 1).unwrap();
+        assert!(days.ends_with("Day13>(),\n    Registered::of::<y2024_day1::Day1>(),\n];\n"));
+        assert!(register_day("", 2023, 1).is_err());
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let toml = "[dependencies]\nutils = { path = \"../utils\" }\nday1 = { path = \"../day1\" }\n\n[dev]\n";
+        assert_eq!(
+            add_dependency(toml, "day2"),
+            "[dependencies]\nutils = { path = \"../utils\" }\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\n\n[dev]\n"
+        );
+    }
+
+    #[test]
+    fn test_add_member() {
+        let globbed = "[workspace]\nmembers = [\"aoc\", \"day*\"]\n";
+        assert_eq!(add_member(globbed, "day5"), Ok(None));
+        let listed = "[workspace]\nmembers = [\"aoc\", \"day1\"]\nresolver = \"2\"\n";
+        assert_eq!(
+            add_member(listed, "day2"),
+            Ok(Some(
+                "[workspace]\nmembers = [\"aoc\", \"day1\", \"day2\"]\nresolver = \"2\"\n"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let lib = render(LIB_TEMPLATE, 2023, 7);
+        assert!(lib.contains("pub struct Day7;") && lib.contains("year = 2023,"));
+        assert!(!lib.contains("{{"));
+        let main = render(MAIN_TEMPLATE, 2022, 7);
+        assert!(main.contains("use y2022_day7::Day7;") && main.contains("cli!(2022, 7)"));
+    }
+}
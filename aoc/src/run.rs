@@ -0,0 +1,266 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use utils::{
+    report::{Format, Record},
+    runner::{DayRun, Registered},
+    PuzzleError, DEFAULT_YEAR,
+};
+
+use crate::days::{days_of, parse_year};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// `aoc run <DAYS> [--year <YEAR>] [--part <N>] [--format <text|json|csv>] [--isolate] [--timeout <SECS>] [--jobs <N>]`
+///
+/// Runs each selected day of the year (2023 by default) against its puzzle
+/// input. A day whose input can't be read is reported and skipped, the command
+/// only fails once every day has run. With json or csv only the records go to
+/// stdout, problems go to stderr.
+///
+/// `--isolate` runs every part on its own thread, so a panic or a part still
+/// running after the timeout (`--timeout`, 60s by default) is reported as that
+/// part's result and the other days still run.
+///
+/// `--jobs` runs the days on that many threads, each loading its own input. The
+/// records are still reported in day order once every day is done, as a table
+/// with the total wall time in text format.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut spec = None;
+    let mut year = DEFAULT_YEAR;
+    let mut part = None;
+    let mut format = Format::Text;
+    let mut timeout = None;
+    let mut jobs = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = parse_year(args.next())?,
+            "--part" => {
+                let n = args.next().and_then(|n| n.parse::<u32>().ok());
+                part = Some(
+                    n.filter(|p| (1..=2).contains(p))
+                        .ok_or("--part needs 1 or 2")?,
+                );
+            }
+            "-f" | "--format" => {
+                let f = args.next().and_then(|f| Format::from_name(f));
+                format = f.ok_or("--format needs text, json or csv")?;
+            }
+            "--isolate" => {
+                timeout.get_or_insert(DEFAULT_TIMEOUT);
+            }
+            "-j" | "--jobs" => {
+                let n = args.next().and_then(|n| n.parse::<usize>().ok());
+                jobs = Some(n.filter(|n| *n > 0).ok_or("--jobs needs a thread count")?);
+            }
+            "--timeout" => {
+                let secs = args.next().and_then(|s| s.parse::<f64>().ok());
+                let secs = secs.filter(|s| *s > 0.0).ok_or("--timeout needs seconds")?;
+                timeout = Some(Duration::from_secs_f64(secs));
+            }
+            s if spec.is_none() => spec = Some(s.to_string()),
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+    let days = parse_days(
+        spec.as_deref().ok_or("run needs a day, a range or 'all'")?,
+        year,
+    )?;
+    let selected: Vec<&Registered> = days_of(year).filter(|r| days.contains(&r.day)).collect();
+    let run_day = |registered: &Registered| match timeout {
+        Some(timeout) => registered.run_puzzle_isolated(part, timeout),
+        None => registered.run_puzzle(part),
+    };
+
+    if format == Format::Csv {
+        println!("{}", Record::CSV_HEADER);
+    }
+    let mut failed = 0;
+    let mut failed_parts = 0;
+    let mut report = |day: u32, run: Result<DayRun, PuzzleError>| match run {
+        Ok(run) => {
+            let records = Record::from_run(&run);
+            failed_parts += records.iter().filter(|r| r.failure.is_some()).count();
+            records
+        }
+        Err(e) => {
+            if format == Format::Text {
+                println!("Day {day}: {e}");
+            } else {
+                eprintln!("Day {day}: {e}");
+            }
+            failed += 1;
+            vec![]
+        }
+    };
+    match jobs {
+        Some(jobs) => {
+            let start = Instant::now();
+            let runs = parallel_map(&selected, jobs, |registered| {
+                let start = Instant::now();
+                (run_day(registered), start.elapsed())
+            });
+            let wall_time = start.elapsed();
+            let busy_time = runs.iter().map(|(_, time)| *time).sum::<Duration>();
+            let mut records = vec![];
+            for (registered, (run, _)) in selected.iter().zip(runs) {
+                records.extend(report(registered.day, run));
+            }
+            if format == Format::Text {
+                print_table(&records);
+                println!(
+                    "{} day(s) in {wall_time:.2?} wall time, {busy_time:.2?} of work on {} thread(s)",
+                    selected.len(),
+                    jobs.min(selected.len())
+                );
+            } else {
+                records
+                    .iter()
+                    .for_each(|record| print_record(format, record));
+            }
+        }
+        None => {
+            for registered in selected {
+                for record in report(registered.day, run_day(registered)) {
+                    print_record(format, &record);
+                }
+            }
+        }
+    }
+    match (failed, failed_parts) {
+        (0, 0) => Ok(()),
+        (f, 0) => Err(format!("{f} day(s) couldn't run")),
+        (0, p) => Err(format!("{p} part(s) failed")),
+        (f, p) => Err(format!("{f} day(s) couldn't run, {p} part(s) failed")),
+    }
+}
+
+fn print_record(format: Format, record: &Record) {
+    match format {
+        Format::Text => match &record.failure {
+            Some(failure) => println!("Day {} part {}: {failure}", record.day, record.part),
+            None => println!("Day {} part {}: {}", record.day, record.part, record.answer),
+        },
+        Format::Json => println!("{}", record.to_json()),
+        Format::Csv => println!("{}", record.to_csv()),
+    }
+}
+
+/// One row per answer, failed parts showing why instead
+fn print_table(records: &[Record]) {
+    let answers: Vec<String> = records
+        .iter()
+        .map(|r| match &r.failure {
+            Some(failure) => failure.to_string(),
+            None => r.answer.clone(),
+        })
+        .collect();
+    let width = answers.iter().map(String::len).max().unwrap_or(0).max(6);
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}",
+        "Day", "Part", "Answer", "parse", "solve"
+    );
+    for (record, answer) in records.iter().zip(&answers) {
+        println!(
+            "{:>3}  {:>4}  {answer:<width$}  {:>10.2?}  {:>10.2?}",
+            record.day, record.part, record.parse_time, record.solve_time
+        );
+    }
+}
+
+/// `f` applied to every item on up to `jobs` threads, results in the items' order
+///
+/// Workers take the next item as they become free, so one slow day doesn't hold
+/// up the ones queued behind it.
+fn parallel_map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.clamp(1, items.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let ndx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(ndx) else {
+                            break done;
+                        };
+                        done.push((ndx, f(item)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    results.sort_by_key(|(ndx, _)| *ndx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// `3`, a range read the way Rust reads it, `1..4` for days 1 to 3 and `1..=4` for
+/// days 1 to 4, or `all`
+///
+/// Only days registered for `year` are returned.
+pub fn parse_days(spec: &str, year: u32) -> Result<Vec<u32>, String> {
+    let bad_spec = || format!("'{spec}' isn't a day, a range like 1..=4 or 'all'");
+    let (first, last) = if spec == "all" {
+        (1, 25)
+    } else if let Some((first, end)) = spec.split_once("..") {
+        let first = first.parse::<u32>().map_err(|_| bad_spec())?;
+        let last = match end.strip_prefix('=') {
+            Some(last) => last.parse::<u32>().map_err(|_| bad_spec())?,
+            None => {
+                let end = end.parse::<u32>().map_err(|_| bad_spec())?;
+                end.checked_sub(1).ok_or_else(bad_spec)?
+            }
+        };
+        (first, last)
+    } else {
+        let day = spec.parse::<u32>().map_err(|_| bad_spec())?;
+        (day, day)
+    };
+    let days: Vec<u32> = days_of(year)
+        .map(|r| r.day)
+        .filter(|day| (first..=last).contains(day))
+        .collect();
+    if days.is_empty() {
+        return Err(format!("no solutions for '{spec}' in {year}"));
+    }
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::run::{parallel_map, parse_days};
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("3", 2023), Ok(vec![3]));
+        assert_eq!(parse_days("2..4", 2023), Ok(vec![2, 3]));
+        assert_eq!(parse_days("2..=3", 2023), Ok(vec![2, 3]));
+        assert!(parse_days("2..2", 2023).is_err());
+        assert!(parse_days("0..0", 2023).is_err());
+        assert_eq!(parse_days("all", 2023).unwrap()[0], 1);
+        assert!(parse_days("all", 2022).is_err());
+        assert!(parse_days("99", 2023).is_err());
+        assert!(parse_days("x..2", 2023).is_err());
+    }
+
+    #[test]
+    fn test_parallel_map() {
+        let items: Vec<u64> = (0..20).collect();
+        let squares = parallel_map(&items, 4, |n| {
+            // Finish out of order
+            std::thread::sleep(std::time::Duration::from_millis(20 - n));
+            n * n
+        });
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert_eq!(parallel_map(&[] as &[u64], 8, |n| *n), []);
+    }
+}
@@ -0,0 +1,82 @@
+use utils::{
+    answers::Answers,
+    fetch::FetchConfig,
+    submit::{Outcome, Submitter},
+    DEFAULT_YEAR,
+};
+
+use crate::days::{days_of, parse_year};
+
+/// `aoc submit <DAY> <PART> [ANSWER] [--year <YEAR>]`
+///
+/// Without an answer the day is run against its puzzle input and its own answer is
+/// sent. Guesses the history in `puzzles/{year}/guesses` rules out are refused
+/// without asking the server, and a correct answer is also added to
+/// `puzzles/{year}/answers`.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut year = DEFAULT_YEAR;
+    let mut positional = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = parse_year(args.next())?,
+            _ => positional.push(arg.clone()),
+        }
+    }
+    let (day, part, answer) = match &positional[..] {
+        [day, part, rest @ ..] if rest.len() <= 1 => (
+            day.parse::<u32>()
+                .map_err(|_| format!("'{day}' isn't a day"))?,
+            part.parse::<u32>()
+                .ok()
+                .filter(|p| (1..=2).contains(p))
+                .ok_or("the part must be 1 or 2")?,
+            rest.first().cloned(),
+        ),
+        _ => return Err("submit needs a day, a part and optionally an answer".to_string()),
+    };
+    let answer = match answer {
+        Some(answer) => answer,
+        None => solve(year, day, part)?,
+    };
+
+    let config = FetchConfig::load().map_err(|e| e.to_string())?;
+    let mut submitter = Submitter::new(config);
+    println!("{year} day {day} part {part}: submitting {answer}");
+    let outcome = submitter
+        .submit(year, day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    println!("{outcome}");
+
+    match outcome {
+        Outcome::Correct => {
+            let mut answers = Answers::load(year).map_err(|e| e.to_string())?;
+            if answers.get(day, part).is_none() {
+                answers.set(day, part, answer.trim());
+                answers.save(year).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        }
+        Outcome::TooHigh | Outcome::TooLow => {
+            let history = submitter.history(year).map_err(|e| e.to_string())?;
+            let (above, below) = history.bounds(day, part);
+            let show = |bound: Option<i64>| bound.map_or("..".to_string(), |n| n.to_string());
+            Err(format!(
+                "the answer is between {} and {}",
+                show(above),
+                show(below)
+            ))
+        }
+        _ => Err("not accepted".to_string()),
+    }
+}
+
+fn solve(year: u32, day: u32, part: u32) -> Result<String, String> {
+    let registered = days_of(year)
+        .find(|r| r.day == day)
+        .ok_or(format!("no solution for {year} day {day}, pass the answer"))?;
+    let run = registered
+        .run_puzzle(Some(part))
+        .map_err(|e| e.to_string())?;
+    Ok(run.parts[0].answer.clone())
+}
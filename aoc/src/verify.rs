@@ -0,0 +1,74 @@
+use utils::{answers::Answers, DEFAULT_YEAR};
+
+use crate::{
+    days::{days_of, parse_year},
+    run::parse_days,
+};
+
+/// `aoc verify [DAYS] [--year <YEAR>] [--record]`
+///
+/// Re-runs the selected days of the year (all by default) against their real
+/// inputs and compares every answer with `puzzles/{year}/answers`. Fails on any mismatch or on a
+/// day that couldn't run. With `--record`, answers that aren't in the store yet
+/// are added to it; confirmed answers are never replaced.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut spec = None;
+    let mut year = DEFAULT_YEAR;
+    let mut record = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = parse_year(args.next())?,
+            "--record" => record = true,
+            s if spec.is_none() => spec = Some(s.to_string()),
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+    let days = parse_days(spec.as_deref().unwrap_or("all"), year)?;
+    let mut answers = Answers::load(year).map_err(|e| e.to_string())?;
+
+    let mut mismatches = 0;
+    let mut failed = 0;
+    let mut recorded = 0;
+    for registered in days_of(year).filter(|r| days.contains(&r.day)) {
+        let run = match registered.run_puzzle(None) {
+            Ok(run) => run,
+            Err(e) => {
+                println!("Day {}: {e}", registered.day);
+                failed += 1;
+                continue;
+            }
+        };
+        for part_run in run.parts {
+            let label = format!("Day {} part {}", run.day, part_run.part);
+            match answers.get(run.day, part_run.part) {
+                Some(expected) if expected == part_run.answer => {
+                    println!("{label}: ok {expected}")
+                }
+                Some(expected) => {
+                    println!(
+                        "{label}: MISMATCH expected {expected}, got {}",
+                        part_run.answer
+                    );
+                    mismatches += 1;
+                }
+                None if record => {
+                    println!("{label}: recorded {}", part_run.answer);
+                    answers.set(run.day, part_run.part, &part_run.answer);
+                    recorded += 1;
+                }
+                None => println!("{label}: unrecorded {}", part_run.answer),
+            }
+        }
+    }
+
+    if recorded > 0 {
+        answers.save(year).map_err(|e| e.to_string())?;
+    }
+    match (mismatches, failed) {
+        (0, 0) => Ok(()),
+        (m, 0) => Err(format!("{m} answer(s) changed")),
+        (0, f) => Err(format!("{f} day(s) couldn't run")),
+        (m, f) => Err(format!("{m} answer(s) changed, {f} day(s) couldn't run")),
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use utils::{
+    examples::{manifest_file_name, read_manifest, ExampleCase},
+    DEFAULT_YEAR,
+};
+
+use crate::days::{crate_name, parse_year, workspace_dir};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// `aoc watch <DAY> [--year <YEAR>] [--interval <MS>]`
+///
+/// Polls the day crate's `src/`, `utils/src/` and the day's files in
+/// `puzzles/{year}/`.
+/// Whenever something changes the day is rebuilt, run on each example in its
+/// manifest and then on the real input, and one line sums up how it went.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut year = DEFAULT_YEAR;
+    let mut day = None;
+    let mut interval = DEFAULT_INTERVAL;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = parse_year(args.next())?,
+            "--interval" => {
+                let ms = args.next().and_then(|ms| ms.parse::<u64>().ok());
+                interval = Duration::from_millis(ms.ok_or("--interval needs milliseconds")?);
+            }
+            d if day.is_none() => {
+                day = Some(d.parse::<u32>().map_err(|_| format!("'{d}' isn't a day"))?)
+            }
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+    let day = day.ok_or("watch needs a day")?;
+
+    let workspace = workspace_dir();
+    let puzzle_dir = utils::year_dir(year);
+    let bin = crate_name(year, day);
+    let watched = Watched {
+        dirs: vec![
+            workspace.join(&bin).join("src"),
+            workspace.join("utils/src"),
+        ],
+        puzzle_dir: puzzle_dir.clone(),
+        prefix: format!("day{day}"),
+    };
+    if !watched.dirs[0].is_dir() {
+        return Err(format!("{} doesn't exist", watched.dirs[0].display()));
+    }
+
+    println!("watching {year} day {day}, Ctrl-C to stop");
+    let mut last = watched.snapshot();
+    println!("{}", cycle(&bin, day, &workspace, &puzzle_dir));
+    loop {
+        thread::sleep(interval);
+        let now = watched.snapshot();
+        if now != last {
+            // Editors often write in several steps, let them finish
+            thread::sleep(interval);
+            last = watched.snapshot();
+            println!("{}", cycle(&bin, day, &workspace, &puzzle_dir));
+        }
+    }
+}
+
+/// The files a day depends on
+struct Watched {
+    dirs: Vec<PathBuf>,
+    puzzle_dir: PathBuf,
+    /// Puzzle files starting with this belong to the day, `day3` for day 3
+    prefix: String,
+}
+
+impl Watched {
+    /// Every watched file with its modification time, in a stable order
+    fn snapshot(&self) -> Vec<(PathBuf, SystemTime)> {
+        let mut files = vec![];
+        for dir in &self.dirs {
+            collect_files(dir, &mut files);
+        }
+        if let Ok(entries) = fs::read_dir(&self.puzzle_dir) {
+            files.extend(entries.flatten().map(|e| e.path()).filter(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                // `day1` must not pick up `day10.txt`
+                name.strip_prefix(&self.prefix)
+                    .is_some_and(|rest| rest.starts_with(['.', '_']))
+            }));
+        }
+        let mut snapshot: Vec<(PathBuf, SystemTime)> = files
+            .into_iter()
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((path, modified))
+            })
+            .collect();
+        snapshot.sort();
+        snapshot
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Builds, checks the examples and runs the real input, returning the summary line
+fn cycle(bin: &str, day: u32, workspace: &Path, puzzle_dir: &Path) -> String {
+    let start = Instant::now();
+    let build = Command::new("cargo")
+        .args(["build", "-q", "--bin", bin])
+        .current_dir(workspace)
+        .output();
+    match build {
+        Ok(output) if output.status.success() => (),
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let first_error = stderr
+                .lines()
+                .find(|l| l.starts_with("error"))
+                .unwrap_or("see cargo build");
+            return format!("{bin}: BUILD FAILED {first_error}");
+        }
+        Err(e) => return format!("{bin}: couldn't run cargo: {e}"),
+    }
+    let run = |input: &Path, part: Option<usize>| -> Result<Vec<String>, String> {
+        let mut command = Command::new("cargo");
+        command
+            .args(["run", "-q", "--bin", bin, "--", "--quiet", "--input"])
+            .arg(input);
+        if let Some(part) = part {
+            command.args(["--part", &part.to_string()]);
+        }
+        let output = command
+            .current_dir(workspace)
+            .output()
+            .map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let panic = stderr
+                .lines()
+                .find(|l| l.contains("panicked"))
+                .unwrap_or("");
+            return Err(format!("failed {panic}").trim().to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    };
+
+    let manifest_path = puzzle_dir.join(manifest_file_name(day));
+    let expected = fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| read_manifest(&text))
+        .map_err(|e| format!("{}: {e}", manifest_path.display()));
+    let examples = match expected {
+        Ok(expected) => summarize_examples(&expected, |input, part| {
+            run(&puzzle_dir.join(input), Some(part))
+        }),
+        Err(e) => e,
+    };
+    let answers = match run(&puzzle_dir.join(format!("day{day}.txt")), None) {
+        Ok(answers) => answers.join(" | "),
+        Err(e) => e,
+    };
+    format!(
+        "{bin} [{:.1}s] examples: {examples}  input: {answers}",
+        start.elapsed().as_secs_f64()
+    )
+}
+
+/// `2/2 pass`, or the count followed by what went wrong with each failure
+///
+/// `run` gets an example file name and a part, and returns what that part printed.
+/// Each (example, part) is run on its own, the way `example_tests!` tests them, so
+/// an example with only a part 2 answer never goes through part 1.
+fn summarize_examples<F: FnMut(&str, usize) -> Result<Vec<String>, String>>(
+    expected: &[ExampleCase],
+    mut run: F,
+) -> String {
+    if expected.is_empty() {
+        return "none".to_string();
+    }
+    let mut failures = vec![];
+    for case in expected {
+        let name = case.input.trim_end_matches(".txt");
+        match run(&case.input, case.part) {
+            Ok(answers) => match answers.first() {
+                Some(got) if *got == case.answer => (),
+                Some(got) => failures.push(format!(
+                    "{name} part{}: got {got}, expected {}",
+                    case.part, case.answer
+                )),
+                None => failures.push(format!("{name} part{}: no answer", case.part)),
+            },
+            Err(e) => failures.push(format!("{name} part{}: {e}", case.part)),
+        }
+    }
+    let passed = expected.len() - failures.len();
+    if failures.is_empty() {
+        format!("{passed}/{} pass", expected.len())
+    } else {
+        format!(
+            "{passed}/{} pass, FAIL {}",
+            expected.len(),
+            failures.join("; ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::examples::{read_manifest, ExampleCase};
+
+    use crate::watch::summarize_examples;
+
+    #[test]
+    fn test_summarize_examples() {
+        let expected =
+            read_manifest("[[example]]\ninput = \"day3_test1.txt\"\npart1 = \"4361\"\npart2 = 7\n")
+                .unwrap();
+        assert_eq!(
+            expected[1],
+            ExampleCase {
+                input: "day3_test1.txt".to_string(),
+                part: 2,
+                answer: "7".to_string()
+            }
+        );
+
+        let mut runs = vec![];
+        let summary = summarize_examples(&expected, |input, part| {
+            runs.push((input.to_string(), part));
+            Ok(vec![["4361", "8"][part - 1].to_string()])
+        });
+        assert_eq!(
+            summary,
+            "1/2 pass, FAIL day3_test1 part2: got 8, expected 7"
+        );
+        // One run per part
+        assert_eq!(
+            runs,
+            [
+                ("day3_test1.txt".to_string(), 1),
+                ("day3_test1.txt".to_string(), 2)
+            ]
+        );
+
+        // A part 2 only example is never run through part 1, which may panic on it
+        let part2_only =
+            read_manifest("[[example]]\ninput = \"day1_test2.txt\"\npart2 = \"281\"\n").unwrap();
+        let summary = summarize_examples(&part2_only, |_, part| match part {
+            2 => Ok(vec!["281".to_string()]),
+            _ => Err("failed".to_string()),
+        });
+        assert_eq!(summary, "1/1 pass");
+
+        assert_eq!(summarize_examples(&[], |_, _| Ok(vec![])), "none");
+        assert_eq!(read_manifest("# nothing yet\n").unwrap(), []);
+    }
+}
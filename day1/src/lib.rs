@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use utils::{Input, Solution};
+
+const NUMBER_WORDS: &[[&str; 2]] = &[
+    ["eight", "8"],
+    ["five", "5"],
+    ["four", "4"],
+    ["nine", "9"],
+    ["one", "1"],
+    ["three", "3"],
+    ["two", "2"],
+    ["seven", "7"],
+    ["six", "6"],
+];
+
+fn parser(s: Input) -> Vec<String> {
+    s.lines().map(str::to_string).collect()
+}
+
+fn solve(amended_calibrations: &[String]) -> u32 {
+    amended_calibrations.iter().map(|s| {
+        let mut nums = s.chars().filter_map(|c| c.to_digit(10));
+        let first = nums.next().unwrap();
+        (first * 10) + nums.nth_back(0).unwrap_or(first)
+    }).collect::<Vec<u32>>().iter().sum()
+}
+
+fn solve2(amended_calibrations: &[String]) -> u32 {
+    // Just replace all of the words with a number, then solve()
+    let replaced_calibrations: Vec<String> = amended_calibrations.iter().map(|s|{
+        let mut new_string = String::new();
+        for n in 0..s.len() {
+            match NUMBER_WORDS.iter().find(|&[word, _word_num]| {
+                match s.get(n..(n+word.len())) {
+                    Some(s_word) => s_word.cmp(word).is_eq(),
+                    None => false,
+                }
+            }) {
+                Some(found_number_word) => {
+                    new_string.push(found_number_word[1].chars().next().unwrap());
+                },
+                None => {
+                    new_string.push(s.chars().nth(n).unwrap());
+                },
+            };
+        }
+        new_string
+    }).collect();
+
+    solve(&replaced_calibrations)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 1;
+
+    type Input = Vec<String>;
+
+    fn parse(input: Input) -> Self::Input {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        solve2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser, solve, solve2};
+
+    utils::example_tests!(
+        day = 1,
+        parser = parser,
+        part1 = |calibrations| solve(&calibrations),
+        part2 = |calibrations| solve2(&calibrations),
+    );
+}
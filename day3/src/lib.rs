@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+use utils::{
+    search::{label_components, Components},
+    Grid, Input, Neighborhood, Point, Solution, StaticGrid,
+};
+
+pub struct Schematic {
+    grid: StaticGrid<char>,
+    /// Each run of digits is one number
+    numbers: Components,
+    /// The value of each number, by its label
+    values: Vec<u32>,
+}
+
+impl Schematic {
+    /// The labels of the numbers touching `p`, each once
+    fn numbers_around(&self, p: Point) -> Vec<usize> {
+        let mut labels: Vec<usize> = self
+            .grid
+            .neighbors8(p)
+            .filter_map(|(q, _)| self.numbers.label(q))
+            .collect();
+        labels.sort_unstable();
+        labels.dedup();
+        labels
+    }
+}
+
+fn is_symbol(c: &char) -> bool {
+    *c != '.' && !c.is_ascii_digit()
+}
+
+fn parser(s: Input) -> Schematic {
+    let grid = StaticGrid::parse(&s, |c, _| c);
+    let numbers = label_components(&grid, Neighborhood::Horizontal, char::is_ascii_digit);
+    // A number's cells run left to right, one digit each
+    let values = numbers
+        .components
+        .iter()
+        .map(|number| {
+            number.cells.iter().fold(0, |value, p| {
+                let digit = grid.get_cell(p.x, p.y).and_then(|c| c.to_digit(10));
+                value * 10 + digit.unwrap()
+            })
+        })
+        .collect();
+    Schematic {
+        grid,
+        numbers,
+        values,
+    }
+}
+
+fn solve(schematic: &Schematic) -> u32 {
+    // Sum the numbers with a symbol next to any of their digits
+    schematic
+        .numbers
+        .components
+        .iter()
+        .zip(&schematic.values)
+        .filter(|(number, _)| {
+            number
+                .cells
+                .iter()
+                .any(|p| schematic.grid.neighbors8(*p).any(|(_, c)| is_symbol(c)))
+        })
+        .map(|(_, value)| value)
+        .sum()
+}
+
+fn solve2(schematic: &Schematic) -> u32 {
+    // 1. Go through all of the cells, looking for an *
+    // 2. Look all around it for 2 numbers
+    // 3. Multiply them, accumulate it
+    let mut running_total = 0;
+    for cell in schematic.grid.positions(|c| *c == '*') {
+        let adjacent_numbers = schematic.numbers_around(cell);
+        if adjacent_numbers.len() == 2 {
+            // Exactly two numbers!
+            running_total +=
+                schematic.values[adjacent_numbers[0]] * schematic.values[adjacent_numbers[1]];
+        }
+    }
+    running_total
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 3;
+
+    type Input = Schematic;
+
+    fn parse(input: Input) -> Self::Input {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        solve2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{load_puzzle_test, Input, Solution, DEFAULT_YEAR};
+
+    use crate::{parser, solve, solve2, Day3};
+
+    utils::example_tests!(
+        day = 3,
+        parser = parser,
+        part1 = |schematic| solve(&schematic),
+        part2 = |schematic| solve2(&schematic),
+    );
+
+    #[test]
+    fn test_parse_numbers() {
+        // Only a number's own digits, up to the edge of the grid
+        let schematic = parser(Input::new("5.12\n*..7\n"));
+        assert_eq!(schematic.values, [5, 12, 7]);
+        assert_eq!(solve(&schematic), 5);
+        assert_eq!(solve2(&schematic), 0);
+
+        // Columns count characters, not bytes
+        let schematic = parser(Input::new("5.12\n€.34\n"));
+        assert_eq!(schematic.values, [5, 12, 34]);
+        assert_eq!(solve(&schematic), 5);
+    }
+
+    #[test]
+    fn test_parts_repeat() {
+        // The parts share one parsed input, in any order and any number of times
+        let schematic = load_puzzle_test(DEFAULT_YEAR, 3, 1, parser);
+        let part1 = Day3::part1(&schematic).to_string();
+        let part2 = Day3::part2(&schematic).to_string();
+        assert_eq!(Day3::part1(&schematic).to_string(), part1);
+        assert_eq!(Day3::part2(&schematic).to_string(), part2);
+        assert_eq!((part1.as_str(), part2.as_str()), ("4361", "467835"));
+    }
+}
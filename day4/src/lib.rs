@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+use utils::{Input, Solution};
+
+pub struct Card {
+    winning_numbers: Vec<u32>,
+    my_numbers: Vec<u32>,
+}
+
+fn parser(s: Input) -> Vec<Card> {
+    let cards: Vec<Card> = s
+        .lines()
+        .map(|card_str| {
+            let (win, mine) = card_str.split_once(':').unwrap().1.split_once('|').unwrap();
+            Card {
+                winning_numbers: utils::ints(win).map(|n| n as u32).collect(),
+                my_numbers: utils::ints(mine).map(|n| n as u32).collect(),
+            }
+        })
+        .collect();
+    cards
+}
+
+fn solve(cards: &[Card]) -> u32 {
+    cards
+        .iter()
+        .map(|card| {
+            let matches: Vec<&u32> = card
+                .winning_numbers
+                .iter()
+                .filter(|win_num| card.my_numbers.contains(win_num))
+                .collect();
+            if matches.is_empty() {
+                0
+            } else {
+                matches.iter().skip(1).fold(1, |a, _| a * 2)
+            }
+        })
+        .sum()
+}
+
+fn solve2(cards: &[Card]) -> u32 {
+    let mut copy_counts = vec![0; cards.len()];
+    for (card_ndx, cur_card) in cards.iter().enumerate() {
+        let matches: Vec<&u32> = cur_card
+            .winning_numbers
+            .iter()
+            .filter(|win_num| cur_card.my_numbers.contains(win_num))
+            .collect();
+        let num_matches = matches.len();
+        let this_card_copies = copy_counts[card_ndx] + 1;
+        for copy_count in copy_counts.iter_mut().skip(card_ndx + 1).take(num_matches) {
+            *copy_count += this_card_copies;
+        }
+    }
+    copy_counts
+        .iter()
+        .fold(0, |a, c| {
+            a + c + 1
+        })
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 4;
+
+    type Input = Vec<Card>;
+
+    fn parse(input: Input) -> Self::Input {
+        parser(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Display {
+        solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Display {
+        solve2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser, solve, solve2};
+
+    utils::example_tests!(
+        day = 4,
+        parser = parser,
+        part1 = |cards| solve(&cards),
+        part2 = |cards| solve2(&cards),
+    );
+}
@@ -0,0 +1,115 @@
+//! Peak heap use and time of each way of loading a puzzle
+//!
+//! `cargo bench -p utils --bench load`, `AOC_BENCH_MB` sets the input size (default 256).
+//! Mapped pages are file-backed and don't show up as heap, which is the point.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    env, fs,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use utils::{ints, load_puzzle_file, PuzzleBytes, PuzzleLines};
+
+struct PeakAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let now = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            let now = CURRENT.fetch_add(new_size, Ordering::Relaxed) + new_size;
+            PEAK.fetch_max(now, Ordering::Relaxed);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+/// Runs `f`, returning its result, how long it took and the most heap it held at once
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration, usize) {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    (result, elapsed, PEAK.load(Ordering::Relaxed) - baseline)
+}
+
+fn generate(path: &Path, megabytes: usize) {
+    let mut out = BufWriter::new(fs::File::create(path).unwrap());
+    let mut written = 0;
+    let mut n: u64 = 1;
+    while written < megabytes * 1024 * 1024 {
+        let line = format!("{} {} -{} {}\n", n % 1000, n % 977, n % 13, n % 100_003);
+        out.write_all(line.as_bytes()).unwrap();
+        written += line.len();
+        n += 1;
+    }
+}
+
+fn main() {
+    let megabytes = env::var("AOC_BENCH_MB")
+        .ok()
+        .and_then(|mb| mb.parse().ok())
+        .unwrap_or(256);
+    let path = env::temp_dir().join(format!("aoc_bench_{megabytes}mb.txt"));
+    generate(&path, megabytes);
+
+    let results = [
+        (
+            "load_puzzle_file (String)",
+            measure(|| load_puzzle_file(&path, |s: String| ints(&s).sum::<i64>())),
+        ),
+        (
+            "PuzzleLines (BufRead)",
+            measure(|| {
+                PuzzleLines::open(&path)
+                    .unwrap()
+                    .map(|line| ints(&line.unwrap()).sum::<i64>())
+                    .sum::<i64>()
+            }),
+        ),
+        (
+            "PuzzleBytes::map (mmap)",
+            measure(|| {
+                let bytes = PuzzleBytes::map(&path).unwrap();
+                ints(std::str::from_utf8(&bytes).unwrap()).sum::<i64>()
+            }),
+        ),
+    ];
+    fs::remove_file(&path).unwrap();
+
+    println!("{megabytes} MB input");
+    println!("{:<28} {:>10} {:>14}", "loader", "time", "peak heap");
+    for (name, (sum, elapsed, peak)) in &results {
+        assert_eq!(*sum, results[0].1 .0, "{name} disagrees");
+        println!(
+            "{name:<28} {:>10.1?} {:>11.1} MB",
+            elapsed,
+            *peak as f64 / (1024.0 * 1024.0)
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::{fmt, fs, path::Path};
+
+use crate::{absolute_path, read_puzzle, year_dir, PuzzleError};
+
+/// Confirmed answers for one year, one per (day, part), kept in `puzzles/{year}/answers`
+///
+/// The file has one `day part answer` line per entry; the answer is the rest of
+/// the line, so it may contain spaces.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub entries: Vec<(u32, u32, String)>,
+}
+
+impl Answers {
+    /// Reads `puzzles/{year}/answers`, a missing file being an empty store
+    pub fn load(year: u32) -> Result<Answers, PuzzleError> {
+        Answers::load_from(&year_dir(year).join("answers"))
+    }
+
+    pub fn load_from(path: &Path) -> Result<Answers, PuzzleError> {
+        match read_puzzle(path) {
+            Ok(text) => Answers::parse(&text).map_err(|(line, message)| PuzzleError::Parse {
+                path: absolute_path(path),
+                line,
+                column: 1,
+                message,
+            }),
+            Err(PuzzleError::MissingFile { .. }) => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, year: u32) -> Result<(), PuzzleError> {
+        self.save_to(&year_dir(year).join("answers"))
+    }
+
+    /// Creates the parent directory if needed, a new year has none yet
+    pub fn save_to(&self, path: &Path) -> Result<(), PuzzleError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| PuzzleError::from_io(dir, e))?;
+        }
+        fs::write(path, self.to_string()).map_err(|e| PuzzleError::from_io(path, e))
+    }
+
+    /// Blank lines and `#` comments are skipped; errors carry the 1-based line
+    pub fn parse(text: &str) -> Result<Answers, (usize, String)> {
+        let mut entries = vec![];
+        for (ndx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || (ndx + 1, "expected `day part answer`".to_string());
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(bad_line());
+            };
+            entries.push((
+                day.parse().map_err(|_| bad_line())?,
+                part.parse().map_err(|_| bad_line())?,
+                answer.trim().to_string(),
+            ));
+        }
+        Ok(Answers { entries })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(d, p, _)| *d == day && *p == part)
+            .map(|(_, _, answer)| answer.as_str())
+    }
+
+    /// Records `answer`, replacing any earlier one for the same (day, part)
+    pub fn set(&mut self, day: u32, part: u32, answer: &str) {
+        self.entries.retain(|(d, p, _)| !(*d == day && *p == part));
+        self.entries.push((day, part, answer.to_string()));
+        self.entries.sort_by_key(|(d, p, _)| (*d, *p));
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day part answer")?;
+        for (day, part, answer) in &self.entries {
+            writeln!(f, "{day} {part} {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::answers::Answers;
+
+    #[test]
+    fn test_answers_round_trip() {
+        let mut answers =
+            Answers::parse("# comment\n3 2 74541112\n\n1 1 elf 3 with 100\n").unwrap();
+        assert_eq!(answers.get(1, 1), Some("elf 3 with 100"));
+        assert_eq!(answers.get(1, 2), None);
+
+        answers.set(3, 2, "1");
+        answers.set(2, 1, "8");
+        assert_eq!(
+            answers.to_string(),
+            "# day part answer\n1 1 elf 3 with 100\n2 1 8\n3 2 1\n"
+        );
+        assert_eq!(Answers::parse(&answers.to_string()), Ok(answers));
+        assert_eq!(Answers::parse("3 x 5").unwrap_err().0, 1);
+    }
+
+    #[test]
+    fn test_missing_store_is_empty() {
+        let path = env::temp_dir().join("aoc_utils_no_answers");
+        assert_eq!(Answers::load_from(&path).unwrap(), Answers::default());
+    }
+}
@@ -0,0 +1,127 @@
+//! Helpers for parsers that work on the raw input bytes
+//!
+//! Puzzle inputs are ASCII, so hot parsers can skip UTF-8 validation and
+//! per-character decoding by taking `&[u8]` from
+//! [`load_puzzle_bytes`](crate::load_puzzle_bytes) or
+//! [`Cli::load_mapped`](crate::cli::Cli::load_mapped).
+
+use crate::{
+    input::{digit_run, too_big},
+    Ints, StaticGrid,
+};
+
+/// Splits on `b'\n'`, dropping a `b'\r'` before it and the empty line after a final newline
+pub fn lines(buf: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let buf = buf.strip_suffix(b"\n").unwrap_or(buf);
+    buf.split(|&b| b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .take_while(move |_| !buf.is_empty())
+}
+
+/// Parses a slice made up only of ASCII digits, `None` if empty, not digits or too big
+pub fn parse_uint(digits: &[u8]) -> Option<u64> {
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u64, |a, &b| {
+        if b.is_ascii_digit() {
+            a.checked_mul(10)?.checked_add((b - b'0') as u64)
+        } else {
+            None
+        }
+    })
+}
+
+/// [`parse_uint`] with an optional leading `-` or `+`
+pub fn parse_int(digits: &[u8]) -> Option<i64> {
+    match digits.split_first() {
+        Some((b'-', rest)) => 0i64.checked_sub_unsigned(parse_uint(rest)?),
+        Some((b'+', rest)) => i64::try_from(parse_uint(rest)?).ok(),
+        _ => i64::try_from(parse_uint(digits)?).ok(),
+    }
+}
+
+/// Every integer in `buf`, a `-` directly in front makes it negative
+pub fn ints(buf: &[u8]) -> Ints<'_> {
+    Ints::new(buf)
+}
+
+/// Every run of digits in `buf`, ignoring any sign
+///
+/// Panics with the position of a number too big for a `u64`, like [`ints`].
+pub fn uints(buf: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let (start, end) = digit_run(buf, pos)?;
+        pos = end;
+        Some(parse_uint(&buf[start..end]).unwrap_or_else(|| too_big(buf, start, end, "u64")))
+    })
+}
+
+/// One cell per byte, one row per line, without decoding anything
+///
+/// Panics if the lines are not all the same length, like [`StaticGrid::parse`]
+pub fn grid(buf: &[u8]) -> StaticGrid<u8> {
+    let rows = lines(buf).map(|row| row.iter().copied());
+    StaticGrid::try_from_rows(rows, |b, _| Ok::<u8, String>(b)).unwrap_or_else(|e| panic!("{e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bytes::{grid, ints, lines, parse_int, parse_uint, uints};
+    use crate::{load_puzzle_test_bytes, Grid, DEFAULT_YEAR};
+
+    #[test]
+    fn test_lines() {
+        let found: Vec<&[u8]> = lines(b"ab\r\ncd\n\nef\n").collect();
+        assert_eq!(found, [&b"ab"[..], b"cd", b"", b"ef"]);
+        assert_eq!(lines(b"ab").count(), 1);
+        assert_eq!(lines(b"").count(), 0);
+        assert_eq!(lines(b"\n").count(), 0);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse_uint(b"467"), Some(467));
+        assert_eq!(parse_uint(b""), None);
+        assert_eq!(parse_uint(b"4a"), None);
+        assert_eq!(parse_uint(b"99999999999999999999"), None);
+        assert_eq!(parse_int(b"-12"), Some(-12));
+        assert_eq!(parse_int(b"+12"), Some(12));
+        assert_eq!(parse_int(b"-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(parse_int(b"-"), None);
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(ints(b"x=-3, y=4").collect::<Vec<_>>(), [-3, 4]);
+        assert_eq!(uints(b"1-3 5").collect::<Vec<_>>(), [1, 3, 5]);
+        assert_eq!(
+            uints(b"18446744073709551615 7").collect::<Vec<_>>(),
+            [u64::MAX, 7]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "1:3: 18446744073709551616 is too big for u64")]
+    fn test_uints_overflow() {
+        uints(b"1 18446744073709551616").for_each(drop);
+    }
+
+    #[test]
+    fn test_grid() {
+        let g = grid(b"467.\r\n...*\r\n");
+        assert_eq!((g.num_rows, g.num_cols), (2, 4));
+        assert_eq!(g.get_cell(3, 1), Some(&b'*'));
+
+        let engine = load_puzzle_test_bytes(DEFAULT_YEAR, 3, 1, grid);
+        assert_eq!((engine.num_rows, engine.num_cols), (10, 10));
+        assert_eq!(parse_uint(&engine.row(0)[..3]), Some(467));
+    }
+
+    #[test]
+    #[should_panic(expected = "2:3: row is 2 wide, the first row is 3")]
+    fn test_grid_ragged() {
+        grid(b"abc\nde\n");
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    cell::RefCell,
+    env,
+    fmt::Display,
+    io::{self, Cursor, Read},
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    puzzle_path, puzzle_test_path, read_puzzle,
+    report::{input_hash, Format, Record},
+    PuzzleBytes, PuzzleError, PuzzleLines,
+};
+
+/// Where a day binary should read its puzzle from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The day's own input, `puzzles/{year}/day{day}.txt` or the embedded copy
+    Puzzle,
+    /// `puzzles/{year}/day{day}_test{n}.txt`
+    Example(u32),
+    File(PathBuf),
+    Stdin,
+}
+
+/// The command line every day binary understands
+///
+/// Build one with [`cli!`](crate::cli!) so the `embedded` feature is honoured.
+#[derive(Debug, Clone)]
+pub struct Cli {
+    pub year: u32,
+    pub day: u32,
+    pub input: InputSource,
+    /// Only run this part, both when `None`
+    pub part: Option<u32>,
+    /// Print only the answers
+    pub quiet: bool,
+    pub format: Format,
+    embedded: Option<&'static str>,
+    loaded: RefCell<Loaded>,
+}
+
+/// What the last load saw, for the records [`Cli::part`] prints
+#[derive(Debug, Clone, Default)]
+struct Loaded {
+    input_hash: String,
+    parse_time: Duration,
+    header_printed: bool,
+}
+
+impl Cli {
+    /// Parses the process arguments, exiting with usage on bad flags
+    pub fn from_args(year: u32, day: u32) -> Cli {
+        match Cli::parse(year, day, env::args().skip(1)) {
+            Ok(Some(cli)) => cli,
+            Ok(None) => {
+                println!("{}", usage(year, day));
+                process::exit(0);
+            }
+            Err(msg) => {
+                eprintln!("day{day}: {msg}\n{}", usage(year, day));
+                process::exit(2);
+            }
+        }
+    }
+
+    /// Parses `args` (without the program name), `None` when help was asked for
+    pub fn parse<I: IntoIterator<Item = String>>(
+        year: u32,
+        day: u32,
+        args: I,
+    ) -> Result<Option<Cli>, String> {
+        let mut cli = Cli {
+            year,
+            day,
+            input: InputSource::Puzzle,
+            part: None,
+            quiet: false,
+            format: Format::Text,
+            embedded: None,
+            loaded: RefCell::default(),
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-i" | "--input" => {
+                    cli.input = match args.next().as_deref() {
+                        Some("-") => InputSource::Stdin,
+                        Some(path) => InputSource::File(PathBuf::from(path)),
+                        None => return Err("--input needs a path or -".to_string()),
+                    }
+                }
+                "-e" | "--example" => {
+                    let n = args.next().and_then(|n| n.parse().ok());
+                    cli.input = InputSource::Example(n.ok_or("--example needs a number")?);
+                }
+                "-p" | "--part" => match args.next().as_deref() {
+                    Some("1") => cli.part = Some(1),
+                    Some("2") => cli.part = Some(2),
+                    _ => return Err("--part needs 1 or 2".to_string()),
+                },
+                "-q" | "--quiet" => cli.quiet = true,
+                "-f" | "--format" => {
+                    let format = args.next().and_then(|f| Format::from_name(&f));
+                    cli.format = format.ok_or("--format needs text, json or csv")?;
+                }
+                "-h" | "--help" => return Ok(None),
+                other => return Err(format!("unexpected argument '{other}'")),
+            }
+        }
+        Ok(Some(cli))
+    }
+
+    /// Uses `text` instead of reading `puzzles/{year}/day{day}.txt` when no other input was chosen
+    pub fn with_embedded(mut self, text: &'static str) -> Cli {
+        self.embedded = Some(text);
+        self
+    }
+
+    /// Reads the selected input and parses it, exiting with the error if it can't be read
+    pub fn load<T, A: From<String>, F: FnOnce(A) -> T>(&self, parser: F) -> T {
+        let text = self.or_exit(self.read_input());
+        let hash = input_hash(text.as_bytes());
+        self.timed_parse(hash, || parser(A::from(text)))
+    }
+
+    /// Like [`load`](Cli::load), but the parser pulls the input line by line
+    ///
+    /// A read error part way through comes to the parser as the last line, see
+    /// [`PuzzleLines`].
+    pub fn load_lines<T, F: FnOnce(PuzzleLines) -> T>(&self, parser: F) -> T {
+        let lines = match (&self.input, self.embedded) {
+            (InputSource::Puzzle, Some(text)) => Ok(PuzzleLines::new(
+                Box::new(Cursor::new(text)),
+                Path::new("<embedded>"),
+            )),
+            (InputSource::Stdin, _) => Ok(PuzzleLines::new(
+                Box::new(io::stdin().lock()),
+                Path::new("<stdin>"),
+            )),
+            _ => PuzzleLines::open(&self.input_path()),
+        };
+        let lines = self.or_exit(lines);
+        // The lines are only read while parsing, so there is nothing to hash up front
+        self.timed_parse(String::new(), || parser(lines))
+    }
+
+    /// Like [`load`](Cli::load), but the parser gets the raw bytes, mapped from
+    /// the file when the input is a file
+    pub fn load_mapped<T, F: FnOnce(&[u8]) -> T>(&self, parser: F) -> T {
+        let bytes = match (&self.input, self.embedded) {
+            (InputSource::Puzzle, Some(text)) => Ok(PuzzleBytes::Static(text.as_bytes())),
+            (InputSource::Stdin, _) => PuzzleBytes::read(io::stdin(), Path::new("<stdin>")),
+            _ => PuzzleBytes::map(&self.input_path()),
+        };
+        let bytes = self.or_exit(bytes);
+        self.timed_parse(input_hash(&bytes), || parser(&bytes))
+    }
+
+    fn timed_parse<T>(&self, input_hash: String, parse: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let parsed = parse();
+        let mut loaded = self.loaded.borrow_mut();
+        loaded.parse_time = start.elapsed();
+        loaded.input_hash = input_hash;
+        parsed
+    }
+
+    /// The file behind the selected input, meaningless for stdin
+    fn input_path(&self) -> PathBuf {
+        match &self.input {
+            InputSource::Puzzle | InputSource::Stdin => puzzle_path(self.year, self.day),
+            InputSource::Example(n) => puzzle_test_path(self.year, self.day, *n),
+            InputSource::File(path) => path.clone(),
+        }
+    }
+
+    fn or_exit<T>(&self, result: Result<T, PuzzleError>) -> T {
+        result.unwrap_or_else(|e| {
+            eprintln!("day{}: {e}", self.day);
+            process::exit(1);
+        })
+    }
+
+    fn read_input(&self) -> Result<String, PuzzleError> {
+        match (&self.input, self.embedded) {
+            (InputSource::Puzzle, Some(text)) => Ok(text.to_string()),
+            (InputSource::Stdin, _) => read_stdin(),
+            _ => read_puzzle(&self.input_path()),
+        }
+    }
+
+    pub fn runs_part(&self, part: u32) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Solves and prints one part, if it was selected
+    ///
+    /// With `--format json` or `csv` the answer is printed as a [`Record`], which
+    /// also carries the timings of the last load and of this part.
+    pub fn part<R: Display, F: FnOnce() -> R>(&self, part: u32, label: &str, solve: F) {
+        if !self.runs_part(part) {
+            return;
+        }
+        let start = Instant::now();
+        let answer = solve().to_string();
+        let solve_time = start.elapsed();
+
+        let mut loaded = self.loaded.borrow_mut();
+        let record = Record {
+            year: self.year,
+            day: self.day,
+            part,
+            answer,
+            parse_time: loaded.parse_time,
+            solve_time,
+            failure: None,
+            input_hash: loaded.input_hash.clone(),
+        };
+        match self.format {
+            Format::Text if self.quiet => println!("{}", record.answer),
+            Format::Text => println!("Solution {part}: {label}: {}", record.answer),
+            Format::Json => println!("{}", record.to_json()),
+            Format::Csv => {
+                if !loaded.header_printed {
+                    println!("{}", Record::CSV_HEADER);
+                    loaded.header_printed = true;
+                }
+                println!("{}", record.to_csv());
+            }
+        }
+    }
+}
+
+fn read_stdin() -> Result<String, PuzzleError> {
+    let path = PathBuf::from("<stdin>");
+    let mut bytes = vec![];
+    io::stdin()
+        .read_to_end(&mut bytes)
+        .map_err(|source| PuzzleError::Io {
+            path: path.clone(),
+            source,
+        })?;
+    String::from_utf8(bytes).map_err(|e| PuzzleError::InvalidUtf8 {
+        path,
+        offset: e.utf8_error().valid_up_to(),
+    })
+}
+
+fn usage(year: u32, day: u32) -> String {
+    format!(
+        "Usage: day{day} [OPTIONS]
+
+Options:
+  -i, --input <PATH|->  Read the puzzle from PATH, or stdin for -
+  -e, --example <N>     Use puzzles/{year}/day{day}_test<N>.txt
+  -p, --part <1|2>      Only solve one part
+  -q, --quiet           Print only the answers
+  -f, --format <FMT>    Print text (default), or json or csv records with timings
+  -h, --help            Print this help"
+    )
+}
+
+/// Parses the command line for a day binary, see [`Cli`]
+///
+/// `cli!(2022, 4)`, or `cli!(4)` for [`DEFAULT_YEAR`](crate::DEFAULT_YEAR). Crates
+/// built with their `embedded` feature default to the input baked in by
+/// [`include_puzzle!`](crate::include_puzzle!).
+#[macro_export]
+macro_rules! cli {
+    ($day:literal) => {{
+        let cli = $crate::cli::Cli::from_args($crate::DEFAULT_YEAR, $day);
+        #[cfg(feature = "embedded")]
+        let cli = cli.with_embedded($crate::include_puzzle!($day));
+        cli
+    }};
+    ($year:literal, $day:literal) => {{
+        let cli = $crate::cli::Cli::from_args($year, $day);
+        #[cfg(feature = "embedded")]
+        let cli = cli.with_embedded($crate::include_puzzle!($year, $day));
+        cli
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::cli::{Cli, InputSource};
+    use crate::report::Format;
+
+    fn parse(args: &[&str]) -> Result<Option<Cli>, String> {
+        Cli::parse(2023, 3, args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_defaults() {
+        let cli = parse(&[]).unwrap().unwrap();
+        assert_eq!(cli.input, InputSource::Puzzle);
+        assert!(cli.runs_part(1) && cli.runs_part(2));
+        assert!(!cli.quiet);
+    }
+
+    #[test]
+    fn test_flags() {
+        let cli = parse(&["--example", "2", "--part", "2", "-q"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(cli.input, InputSource::Example(2));
+        assert!(!cli.runs_part(1) && cli.runs_part(2));
+        assert!(cli.quiet);
+
+        let cli = parse(&["-i", "-"]).unwrap().unwrap();
+        assert_eq!(cli.input, InputSource::Stdin);
+        let cli = parse(&["--input", "big.txt"]).unwrap().unwrap();
+        assert_eq!(cli.input, InputSource::File(PathBuf::from("big.txt")));
+        let cli = parse(&["-f", "csv"]).unwrap().unwrap();
+        assert_eq!(cli.format, Format::Csv);
+    }
+
+    #[test]
+    fn test_bad_flags() {
+        assert!(parse(&["--help"]).unwrap().is_none());
+        assert!(parse(&["--part", "3"]).is_err());
+        assert!(parse(&["--example"]).is_err());
+        assert!(parse(&["--frobnicate"]).is_err());
+        assert!(parse(&["--format", "xml"]).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+pub use utils_manifest::{read_manifest, ExampleCase};
+
+/// One `<pre><code>` block from a puzzle page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// Which part's description the block appeared in, 1 or 2
+    pub part: u32,
+    pub text: String,
+}
+
+/// An emphasized answer from a puzzle page, `<code><em>142</em></code>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleAnswer {
+    pub part: u32,
+    /// Index into [`PuzzlePage::examples`] of the example this answer is for
+    pub example: usize,
+    pub answer: String,
+}
+
+/// What could be pulled out of a saved puzzle page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzlePage {
+    /// The season from the `<title>`, `Day 4 - Advent of Code 2023`
+    pub year: Option<u32>,
+    /// The day from the `--- Day N: ... ---` heading, if there was one
+    pub day: Option<u32>,
+    /// Every example block, in page order
+    pub examples: Vec<Example>,
+    /// At most one answer per part
+    pub answers: Vec<ExampleAnswer>,
+}
+
+impl PuzzlePage {
+    /// Pulls the examples and their answers out of a puzzle page's HTML
+    ///
+    /// Each part's answer is taken to be the last emphasized code in its description
+    /// and to belong to the last example block before it. A part 2 without blocks of
+    /// its own is assumed to reuse the last part 1 example.
+    pub fn parse(html: &str) -> PuzzlePage {
+        let mut page = PuzzlePage {
+            year: parse_year(html),
+            day: parse_day(html),
+            ..Default::default()
+        };
+        for (part, article) in (1..).zip(tag_contents(html, "<article", "</article>")) {
+            page.examples
+                .extend(
+                    tag_contents(article, "<pre><code>", "</code></pre>").map(|block| Example {
+                        part,
+                        text: decode_entities(&strip_tags(block)),
+                    }),
+                );
+            let answer = emphasized_code(article).last();
+            if let (Some(answer), Some(example)) = (answer, page.examples.len().checked_sub(1)) {
+                page.answers.push(ExampleAnswer {
+                    part,
+                    example,
+                    answer,
+                });
+            }
+        }
+        page
+    }
+
+    /// Renders the answers as a test manifest, numbering examples from 1 in page order
+    pub fn manifest(&self, day: u32) -> String {
+        let mut toml = String::from(
+            "# Extracted from the puzzle page, check the answers before trusting them\n",
+        );
+        for (ndx, _) in self.examples.iter().enumerate() {
+            let answers: Vec<&ExampleAnswer> =
+                self.answers.iter().filter(|a| a.example == ndx).collect();
+            if answers.is_empty() {
+                continue;
+            }
+            toml.push_str("\n[[example]]\n");
+            toml.push_str(&format!(
+                "input = \"{}\"\n",
+                example_file_name(day, ndx as u32 + 1)
+            ));
+            for answer in answers {
+                toml.push_str(&format!(
+                    "part{} = \"{}\"\n",
+                    answer.part,
+                    answer.answer.replace('\\', "\\\\").replace('"', "\\\"")
+                ));
+            }
+        }
+        toml
+    }
+}
+
+/// File name of the `n`th (1-based) example for `day`, as `load_puzzle_test` reads it
+pub fn example_file_name(day: u32, test_number: u32) -> String {
+    format!("day{day}_test{test_number}.txt")
+}
+
+/// File name of the example test manifest for `day`
+pub fn manifest_file_name(day: u32) -> String {
+    format!("day{day}_tests.toml")
+}
+
+fn parse_year(html: &str) -> Option<u32> {
+    let title = tag_contents(html, "<title", "</title>").next()?;
+    let rest = &title[title.find("Advent of Code ")? + 15..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+fn parse_day(html: &str) -> Option<u32> {
+    let heading = tag_contents(html, "<h2", "</h2>").next()?;
+    let rest = &heading[heading.find("Day ")? + 4..];
+    rest[..rest.find(|c: char| !c.is_ascii_digit())?]
+        .parse()
+        .ok()
+}
+
+/// Everything between each `open` (up to the end of that tag) and the next `close`
+fn tag_contents<'a>(
+    html: &'a str,
+    open: &'a str,
+    close: &'a str,
+) -> impl Iterator<Item = &'a str> + 'a {
+    let mut rest = html;
+    std::iter::from_fn(move || {
+        let start = rest.find(open)?;
+        let after_open = &rest[start + open.len()..];
+        // `open` may be a prefix like `<article`, skip to the end of the tag
+        let body_start = if open.ends_with('>') {
+            0
+        } else {
+            after_open.find('>')? + 1
+        };
+        let body = &after_open[body_start..];
+        let end = body.find(close)?;
+        rest = &body[end + close.len()..];
+        Some(&body[..end])
+    })
+}
+
+/// The text of `<code><em>..</em></code>` and `<em><code>..</code></em>`, in page order
+fn emphasized_code(article: &str) -> impl Iterator<Item = String> + '_ {
+    let mut found: Vec<(usize, &str)> = vec![];
+    for (open, close) in [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ] {
+        let mut offset = 0;
+        while let Some(start) = article[offset..].find(open) {
+            let body_start = offset + start + open.len();
+            let Some(len) = article[body_start..].find(close) else {
+                break;
+            };
+            found.push((body_start, &article[body_start..body_start + len]));
+            offset = body_start + len + close.len();
+        }
+    }
+    found.sort_by_key(|(at, _)| *at);
+    found
+        .into_iter()
+        .map(|(_, text)| decode_entities(&strip_tags(text)))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::{Example, ExampleAnswer, PuzzlePage};
+
+    const PAGE: &str = r#"<html><head><title>Day 4 - Advent of Code 2023</title></head><body><main>
+<article class="day-desc"><h2>--- Day 4: Scratchcards ---</h2><p>For example:</p>
+<pre><code>Card 1: 41 48 | 83 86
+Card 2: 13 &lt;32&gt; | <em>61</em> 30
+</code></pre>
+<p>Card <code>1</code> is worth <code><em>8</em></code> points, so the pile is worth <code><em>13</em></code> points.</p>
+</article>
+<p>Your puzzle answer was <code>22897</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>You end up with <em><code>30</code></em> scratchcards.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_parse_page() {
+        let page = PuzzlePage::parse(PAGE);
+        assert_eq!((page.year, page.day), (Some(2023), Some(4)));
+        assert_eq!(
+            page.examples,
+            [Example {
+                part: 1,
+                text: "Card 1: 41 48 | 83 86\nCard 2: 13 <32> | 61 30\n".to_string(),
+            }]
+        );
+        assert_eq!(
+            page.answers,
+            [
+                ExampleAnswer {
+                    part: 1,
+                    example: 0,
+                    answer: "13".to_string(),
+                },
+                ExampleAnswer {
+                    part: 2,
+                    example: 0,
+                    answer: "30".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manifest() {
+        let page = PuzzlePage::parse(PAGE);
+        assert_eq!(
+            page.manifest(4),
+            "# Extracted from the puzzle page, check the answers before trusting them
+
+[[example]]
+input = \"day4_test1.txt\"
+part1 = \"13\"
+part2 = \"30\"
+"
+        );
+    }
+
+    #[test]
+    fn test_parse_part_one_only() {
+        let part_one = &PAGE[..PAGE.find("<p>Your puzzle").unwrap()];
+        let page = PuzzlePage::parse(part_one);
+        assert_eq!(page.examples.len(), 1);
+        assert_eq!(page.answers.len(), 1);
+        assert!(PuzzlePage::parse("<html></html>").examples.is_empty());
+    }
+}
@@ -0,0 +1,344 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::puzzle_dir;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variables that override the config file
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+const USER_AGENT: &str = "github.com/justdaniel-gh/advent-of-code-2023 (utils::fetch)";
+
+/// Marker in the puzzle directory whose mtime records the last request, across runs
+const LAST_REQUEST_MARKER: &str = ".last_fetch";
+
+/// The HTTP client every request to the server goes through
+pub(crate) fn new_agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .user_agent(USER_AGENT)
+        .timeout_global(Some(Duration::from_secs(30)))
+        .build()
+        .into()
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// No session token in the environment or config file
+    MissingSession,
+    /// The server answered, but not with a 200
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// The request never got an answer
+    Transport {
+        url: String,
+        message: String,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => write!(
+                f,
+                "no session token, set {SESSION_ENV} or add `session = ...` to the config file"
+            ),
+            FetchError::Status { url, status } => write!(f, "{url} returned HTTP {status}"),
+            FetchError::Transport { url, message } => write!(f, "{url}: {message}"),
+            FetchError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Where to download from and who to download as
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    /// The site's URL, inputs are at `{base_url}/{year}/day/{day}/input`
+    pub base_url: String,
+    pub session: String,
+    /// Minimum time between two requests to the server
+    pub min_interval: Duration,
+}
+
+impl FetchConfig {
+    pub fn new(base_url: &str, session: &str) -> FetchConfig {
+        FetchConfig {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            min_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// Reads `session` and `base_url` from the config file, letting
+    /// `AOC_SESSION` / `AOC_BASE_URL` override them
+    ///
+    /// The config file is `AOC_CONFIG` if set, otherwise `~/.config/aoc/config`,
+    /// with one `key = value` per line.
+    pub fn load() -> Result<FetchConfig, FetchError> {
+        let file_values = config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| parse_config(&text))
+            .unwrap_or_default();
+        let lookup = |env_key: &str, file_key: &str| {
+            env::var(env_key).ok().or_else(|| {
+                file_values
+                    .iter()
+                    .find(|(k, _)| k == file_key)
+                    .map(|(_, v)| v.clone())
+            })
+        };
+
+        let session = lookup(SESSION_ENV, "session").ok_or(FetchError::MissingSession)?;
+        let base_url = lookup(BASE_URL_ENV, "base_url");
+        Ok(FetchConfig::new(
+            base_url.as_deref().unwrap_or(DEFAULT_BASE_URL),
+            &session,
+        ))
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    env::var_os(CONFIG_ENV)
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/aoc/config")))
+}
+
+fn parse_config(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().trim_matches('"').to_string()))
+        .collect()
+}
+
+/// Downloads puzzle inputs into the `puzzles/` layout, one request at a time
+pub struct Fetcher {
+    config: FetchConfig,
+    puzzle_dir: PathBuf,
+    agent: ureq::Agent,
+    throttle: Throttle,
+}
+
+impl Fetcher {
+    pub fn new(config: FetchConfig) -> Fetcher {
+        Fetcher {
+            config,
+            puzzle_dir: puzzle_dir(),
+            agent: new_agent(),
+            throttle: Throttle::default(),
+        }
+    }
+
+    /// Caches into `dir` instead of the workspace `puzzles/`
+    pub fn with_puzzle_dir(mut self, dir: &Path) -> Fetcher {
+        self.puzzle_dir = dir.to_path_buf();
+        self
+    }
+
+    /// Returns the path of the day's input, downloading it into `{year}/` only if it
+    /// isn't cached yet
+    ///
+    /// An empty file counts as missing, that's what `aoc new` leaves behind.
+    pub fn fetch_input(&mut self, year: u32, day: u32) -> Result<PathBuf, FetchError> {
+        let year_dir = self.puzzle_dir.join(year.to_string());
+        let path = year_dir.join(format!("day{day}.txt"));
+        if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok(path);
+        }
+
+        let url = format!("{}/{year}/day/{day}/input", self.config.base_url);
+        let body = self.get(&url)?;
+
+        let io_err = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| FetchError::Io { path, source }
+        };
+        fs::create_dir_all(&year_dir).map_err(io_err(&year_dir))?;
+        // Write then rename so an interrupted download never looks cached
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, body).map_err(io_err(&partial))?;
+        fs::rename(&partial, &path).map_err(io_err(&path))?;
+        Ok(path)
+    }
+
+    fn get(&mut self, url: &str) -> Result<Vec<u8>, FetchError> {
+        self.throttle
+            .wait_for_turn(&self.puzzle_dir, self.config.min_interval);
+        let response = self
+            .agent
+            .get(url)
+            .header("Cookie", &format!("session={}", self.config.session))
+            .call();
+        self.throttle.mark_request(&self.puzzle_dir);
+
+        match response {
+            Ok(mut response) => {
+                response
+                    .body_mut()
+                    .read_to_vec()
+                    .map_err(|e| FetchError::Transport {
+                        url: url.to_string(),
+                        message: e.to_string(),
+                    })
+            }
+            Err(ureq::Error::StatusCode(status)) => Err(FetchError::Status {
+                url: url.to_string(),
+                status,
+            }),
+            Err(e) => Err(FetchError::Transport {
+                url: url.to_string(),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+/// Spaces out requests to the server, fetches and submissions alike
+#[derive(Debug, Default)]
+pub(crate) struct Throttle {
+    last_request: Option<Instant>,
+}
+
+impl Throttle {
+    /// Sleeps until `min_interval` has passed since the last request from this
+    /// process or, going by the marker file in `puzzle_dir`, any earlier one
+    pub(crate) fn wait_for_turn(&self, puzzle_dir: &Path, min_interval: Duration) {
+        let since_marker = fs::metadata(puzzle_dir.join(LAST_REQUEST_MARKER))
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| SystemTime::now().duration_since(t).ok());
+        let since_last = self.last_request.map(|t| t.elapsed());
+        let elapsed = match (since_marker, since_last) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let Some(remaining) = elapsed.and_then(|e| min_interval.checked_sub(e)) {
+            thread::sleep(remaining);
+        }
+    }
+
+    pub(crate) fn mark_request(&mut self, puzzle_dir: &Path) {
+        self.last_request = Some(Instant::now());
+        // Best effort, the in-process limit still applies without it
+        let _ = fs::write(puzzle_dir.join(LAST_REQUEST_MARKER), b"");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        fetch::{parse_config, FetchConfig, FetchError, Fetcher},
+        stand_in::{self, Request},
+    };
+
+    /// Serves `/2023/day/{n}/input` to requests carrying `session=secret`, 404 otherwise
+    fn stand_in_server() -> (String, Arc<AtomicUsize>) {
+        stand_in::stand_in_server(|request: &Request| {
+            match request.path.strip_prefix("/2023/day/") {
+                Some(rest) if request.authorized && rest.ends_with("/input") => (
+                    200,
+                    format!("input for day {}\n", rest.trim_end_matches("/input")),
+                ),
+                _ => (404, String::new()),
+            }
+        })
+    }
+
+    fn temp_puzzle_dir(name: &str) -> std::path::PathBuf {
+        let dir = env::temp_dir().join(format!("aoc_fetch_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let (base_url, hits) = stand_in_server();
+        let dir = temp_puzzle_dir("cache");
+        let mut config = FetchConfig::new(&base_url, "secret");
+        config.min_interval = Duration::from_millis(200);
+        let mut fetcher = Fetcher::new(config).with_puzzle_dir(&dir);
+
+        let path = fetcher.fetch_input(2023, 3).unwrap();
+        assert_eq!(path, dir.join("2023/day3.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input for day 3\n");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Cached, no second request
+        fetcher.fetch_input(2023, 3).unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // A different day has to wait for the rate limit
+        let start = Instant::now();
+        fetcher.fetch_input(2023, 4).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_rejected() {
+        let (base_url, _) = stand_in_server();
+        let dir = temp_puzzle_dir("rejected");
+        let mut config = FetchConfig::new(&base_url, "wrong");
+        config.min_interval = Duration::ZERO;
+        let mut fetcher = Fetcher::new(config).with_puzzle_dir(&dir);
+
+        let err = fetcher.fetch_input(2023, 1).unwrap_err();
+        assert!(matches!(err, FetchError::Status { status: 404, .. }));
+        assert!(!dir.join("2023/day1.txt").exists());
+
+        // Only 2023 is served
+        let mut config = FetchConfig::new(&base_url, "secret");
+        config.min_interval = Duration::ZERO;
+        let mut fetcher = Fetcher::new(config).with_puzzle_dir(&dir);
+        assert!(fetcher.fetch_input(2022, 1).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let values = parse_config("# mine\nsession = \"abc123\"\n\nbase_url=http://x\n");
+        assert_eq!(
+            values,
+            [
+                ("session".to_string(), "abc123".to_string()),
+                ("base_url".to_string(), "http://x".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::{fmt, ops::Deref};
+
+use crate::{bytes::parse_uint, ParseError, StaticGrid};
+
+/// Puzzle text with line endings normalized and trailing whitespace removed
+///
+/// Parsers can take an `Input` instead of a `String` and get the common ways of
+/// slicing up a puzzle without worrying about CRLF files or the final newline.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn new(text: &str) -> Input {
+        Input {
+            text: text.replace("\r\n", "\n").trim_end().to_string(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /// Blocks of lines separated by a blank line
+    pub fn paragraphs(&self) -> std::str::Split<'_, &'static str> {
+        self.text.split("\n\n")
+    }
+
+    /// One cell per character, one row per line
+    ///
+    /// Panics if the lines are not all the same length, see [`StaticGrid::parse`]
+    pub fn char_grid(&self) -> StaticGrid<char> {
+        StaticGrid::parse(&self.text, |c, _| c)
+    }
+
+    /// Every integer in the input, see [`ints`]
+    pub fn ints(&self) -> Ints<'_> {
+        ints(&self.text)
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Input::new(&text)
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Iterates over every integer in `s`, skipping whatever is between them
+///
+/// A `-` directly in front of the digits makes the number negative. Panics with the
+/// line and column of a number that doesn't fit an `i64`, rather than getting it wrong.
+pub fn ints(s: &str) -> Ints<'_> {
+    Ints::new(s.as_bytes())
+}
+
+pub struct Ints<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Ints<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Ints<'a> {
+        Ints { bytes, pos: 0 }
+    }
+}
+
+/// Panics about the number at `start..end` of `bytes`, pointing at where it is
+pub(crate) fn too_big(bytes: &[u8], start: usize, end: usize, type_name: &str) -> ! {
+    let before = String::from_utf8_lossy(&bytes[..start]);
+    let number = String::from_utf8_lossy(&bytes[start..end]);
+    let error = ParseError::at_offset(
+        &before,
+        before.len(),
+        format!("{number} is too big for {type_name}"),
+    );
+    panic!("{error}")
+}
+
+/// Start and end of the first run of ASCII digits in `bytes` at or after `pos`
+pub(crate) fn digit_run(bytes: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let start = pos + bytes[pos..].iter().position(u8::is_ascii_digit)?;
+    let end = start
+        + bytes[start..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(bytes.len() - start);
+    Some((start, end))
+}
+
+impl<'a> Iterator for Ints<'a> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = digit_run(self.bytes, self.pos)?;
+        self.pos = end;
+
+        let negative = start > 0 && self.bytes[start - 1] == b'-';
+        let value = parse_uint(&self.bytes[start..end]).and_then(|magnitude| {
+            if negative {
+                0i64.checked_sub_unsigned(magnitude)
+            } else {
+                i64::try_from(magnitude).ok()
+            }
+        });
+        match value {
+            Some(value) => Some(value),
+            None => too_big(self.bytes, start - negative as usize, end, "i64"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ints, Input};
+
+    #[test]
+    fn test_input_normalizes() {
+        let input = Input::from("ab\r\ncd\r\n\r\nef\n\n".to_string());
+        assert_eq!(input.as_str(), "ab\ncd\n\nef");
+        assert_eq!(input.lines().collect::<Vec<_>>(), ["ab", "cd", "", "ef"]);
+        assert_eq!(input.paragraphs().collect::<Vec<_>>(), ["ab\ncd", "ef"]);
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let grid = Input::new("abc\ndef\n").char_grid();
+        assert_eq!(grid.num_rows, 2);
+        assert_eq!(grid.num_cols, 3);
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+    }
+
+    #[test]
+    #[should_panic(expected = "2:3: row is 2 wide, the first row is 3")]
+    fn test_char_grid_ragged() {
+        Input::new("abc\nde\n").char_grid();
+    }
+
+    #[test]
+    fn test_ints() {
+        let found: Vec<i64> = ints("Card 1: 41 -48 | x-3,7").collect();
+        assert_eq!(found, [1, 41, -48, -3, 7]);
+        assert_eq!(Input::new("12\n").ints().collect::<Vec<_>>(), [12]);
+        assert_eq!(ints("none").next(), None);
+        assert_eq!(
+            ints("1 -9223372036854775808 2").collect::<Vec<_>>(),
+            [1, i64::MIN, 2]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "2:3: 9223372036854775808 is too big for i64")]
+    fn test_ints_overflow() {
+        ints("1\nx 9223372036854775808").for_each(drop);
+    }
+}
@@ -2,29 +2,311 @@
 
 use std::{
     cmp::Ordering,
+    env,
+    error::Error,
     fmt::{self, Display},
-    fs,
-    path::Path,
+    fs, io,
+    path::{Path, PathBuf},
 };
 
 use enum_iterator::Sequence;
 
-fn load_puzzle<T, F: FnOnce(String) -> T>(puzzle_path: &Path, parser: F) -> T {
-    parser(String::from_utf8(fs::read(puzzle_path).expect("Unable to open input!")).unwrap())
+pub mod answers;
+pub mod bytes;
+pub mod cli;
+pub mod examples;
+pub mod fetch;
+mod input;
+pub mod report;
+pub mod runner;
+pub mod search;
+#[cfg(test)]
+mod stand_in;
+mod stream;
+pub mod submit;
+pub mod timing;
+
+pub use input::{ints, Input, Ints};
+pub use runner::Solution;
+pub use stream::{try_load_puzzle_lines, try_load_puzzle_mapped, PuzzleBytes, PuzzleLines};
+pub use utils_macros::{example_tests, include_puzzle, include_puzzle_test};
+
+/// Everything that can go wrong between a puzzle file on disk and a parsed puzzle
+#[derive(Debug)]
+pub enum PuzzleError {
+    /// The puzzle file does not exist; `path` is the absolute path that was tried
+    MissingFile { path: PathBuf },
+    /// The puzzle file exists but could not be read
+    Io { path: PathBuf, source: io::Error },
+    /// The puzzle file is not valid UTF-8; `offset` is the first invalid byte
+    InvalidUtf8 { path: PathBuf, offset: usize },
+    /// The parser rejected the input
+    Parse {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
 }
 
-pub fn load_puzzle_data<T, F: FnOnce(String) -> T>(day: u32, parser: F) -> T {
-    let puzzle_filename = format!("puzzles/day{day}.txt");
-    let puzzle_path = Path::new(&puzzle_filename);
-    load_puzzle(puzzle_path, parser)
+impl PuzzleError {
+    fn from_io(puzzle_path: &Path, source: io::Error) -> PuzzleError {
+        let path = absolute_path(puzzle_path);
+        if source.kind() == io::ErrorKind::NotFound {
+            PuzzleError::MissingFile { path }
+        } else {
+            PuzzleError::Io { path, source }
+        }
+    }
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PuzzleError::MissingFile { path } => {
+                write!(f, "puzzle input not found: {}", path.display())
+            }
+            PuzzleError::Io { path, source } => {
+                write!(
+                    f,
+                    "unable to read puzzle input {}: {source}",
+                    path.display()
+                )
+            }
+            PuzzleError::InvalidUtf8 { path, offset } => {
+                write!(f, "{}: invalid UTF-8 at byte {offset}", path.display())
+            }
+            PuzzleError::Parse {
+                path,
+                line,
+                column,
+                message,
+            } => write!(f, "{}:{line}:{column}: {message}", path.display()),
+        }
+    }
+}
+
+impl Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PuzzleError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned by fallible parsers, pointing at the offending spot in the input
+///
+/// `line` and `column` are 1-based, like an editor shows them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Builds an error from a byte offset into `input`, working out the line and column
+    ///
+    /// An offset inside a character points at that character.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> ParseError {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError::new(line, column, message)
+    }
 }
 
-pub fn load_puzzle_test<T, F: FnOnce(String) -> T>(day: u32, test_number: u32, parser: F) -> T {
-    let puzzle_filename = format!("../puzzles/day{day}_test{test_number}.txt");
-    let puzzle_path = Path::new(&puzzle_filename);
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+fn absolute_path(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn read_puzzle(puzzle_path: &Path) -> Result<String, PuzzleError> {
+    let bytes = fs::read(puzzle_path).map_err(|e| PuzzleError::from_io(puzzle_path, e))?;
+    String::from_utf8(bytes).map_err(|e| PuzzleError::InvalidUtf8 {
+        path: absolute_path(puzzle_path),
+        offset: e.utf8_error().valid_up_to(),
+    })
+}
+
+/// The loaders hand parsers either the raw `String` or a normalized [`Input`],
+/// whichever the parser asks for
+fn load_puzzle<T, A: From<String>, F: FnOnce(A) -> T>(puzzle_path: &Path, parser: F) -> T {
+    let text = read_puzzle(puzzle_path).unwrap_or_else(|e| panic!("Unable to open input! {e}"));
+    parser(A::from(text))
+}
+
+/// Environment variable that, when set, names the puzzle directory directly
+pub const PUZZLE_DIR_ENV: &str = "AOC_PUZZLE_DIR";
+
+const PUZZLE_DIR_NAME: &str = "puzzles";
+
+/// Walks up from `start` looking for a directory containing `puzzles/`
+fn find_puzzle_dir_from(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PUZZLE_DIR_NAME))
+        .find(|candidate| candidate.is_dir())
+}
+
+fn resolve_puzzle_dir(dir_override: Option<PathBuf>) -> PathBuf {
+    if let Some(dir) = dir_override {
+        return dir;
+    }
+    // Set by cargo for `cargo run` / `cargo test`, points at the day crate
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    // Wherever the binary ended up, usually target/<profile>/
+    let exe_dir = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    // Where utils was built from, for binaries run outside of cargo
+    let build_dir = Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")));
+    let cwd = env::current_dir().ok();
+
+    [manifest_dir, exe_dir, build_dir, cwd]
+        .into_iter()
+        .flatten()
+        .find_map(|start| find_puzzle_dir_from(&start))
+        .unwrap_or_else(|| PathBuf::from(PUZZLE_DIR_NAME))
+}
+
+/// Locates the workspace `puzzles/` directory, independent of the working directory
+///
+/// `AOC_PUZZLE_DIR` wins if set, otherwise the first `puzzles/` found walking up from
+/// the crate being run, the executable, the workspace utils was built in and the CWD.
+pub fn puzzle_dir() -> PathBuf {
+    resolve_puzzle_dir(env::var_os(PUZZLE_DIR_ENV).map(PathBuf::from))
+}
+
+/// The season a day belongs to when nothing says otherwise
+pub const DEFAULT_YEAR: u32 = 2023;
+
+/// Where one season's puzzles live, `puzzles/{year}/`
+pub fn year_dir(year: u32) -> PathBuf {
+    puzzle_dir().join(year.to_string())
+}
+
+pub fn puzzle_path(year: u32, day: u32) -> PathBuf {
+    year_dir(year).join(format!("day{day}.txt"))
+}
+
+pub fn puzzle_test_path(year: u32, day: u32, test_number: u32) -> PathBuf {
+    year_dir(year).join(format!("day{day}_test{test_number}.txt"))
+}
+
+pub fn load_puzzle_file<T, A: From<String>, F: FnOnce(A) -> T>(puzzle_path: &Path, parser: F) -> T {
     load_puzzle(puzzle_path, parser)
 }
 
+pub fn load_puzzle_data<T, A: From<String>, F: FnOnce(A) -> T>(
+    year: u32,
+    day: u32,
+    parser: F,
+) -> T {
+    load_puzzle(&puzzle_path(year, day), parser)
+}
+
+pub fn load_puzzle_test<T, A: From<String>, F: FnOnce(A) -> T>(
+    year: u32,
+    day: u32,
+    test_number: u32,
+    parser: F,
+) -> T {
+    load_puzzle(&puzzle_test_path(year, day, test_number), parser)
+}
+
+/// Hands the parser the day's input without checking that it is UTF-8, see [`bytes`]
+pub fn load_puzzle_bytes<T, F: FnOnce(&[u8]) -> T>(year: u32, day: u32, parser: F) -> T {
+    load_bytes(&puzzle_path(year, day), parser)
+}
+
+pub fn load_puzzle_test_bytes<T, F: FnOnce(&[u8]) -> T>(
+    year: u32,
+    day: u32,
+    test_number: u32,
+    parser: F,
+) -> T {
+    load_bytes(&puzzle_test_path(year, day, test_number), parser)
+}
+
+fn load_bytes<T, F: FnOnce(&[u8]) -> T>(puzzle_path: &Path, parser: F) -> T {
+    let bytes = fs::read(puzzle_path)
+        .map_err(|e| PuzzleError::from_io(puzzle_path, e))
+        .unwrap_or_else(|e| panic!("Unable to open input! {e}"));
+    parser(&bytes)
+}
+
+/// Runs a parser over puzzle text that is already in memory, e.g. from [`include_puzzle!`]
+pub fn parse_puzzle<T, A: From<String>, F: FnOnce(A) -> T>(text: &str, parser: F) -> T {
+    parser(A::from(text.to_string()))
+}
+
+/// Loads and parses any puzzle file, reporting failures instead of panicking
+pub fn try_load_puzzle_file<T, A, E, F>(puzzle_path: &Path, parser: F) -> Result<T, PuzzleError>
+where
+    A: From<String>,
+    E: Into<ParseError>,
+    F: FnOnce(A) -> Result<T, E>,
+{
+    parser(A::from(read_puzzle(puzzle_path)?)).map_err(|e| {
+        let ParseError {
+            line,
+            column,
+            message,
+        } = e.into();
+        PuzzleError::Parse {
+            path: absolute_path(puzzle_path),
+            line,
+            column,
+            message,
+        }
+    })
+}
+
+pub fn try_load_puzzle_data<T, A, E, F>(year: u32, day: u32, parser: F) -> Result<T, PuzzleError>
+where
+    A: From<String>,
+    E: Into<ParseError>,
+    F: FnOnce(A) -> Result<T, E>,
+{
+    try_load_puzzle_file(&puzzle_path(year, day), parser)
+}
+
+pub fn try_load_puzzle_test<T, A, E, F>(
+    year: u32,
+    day: u32,
+    test_number: u32,
+    parser: F,
+) -> Result<T, PuzzleError>
+where
+    A: From<String>,
+    E: Into<ParseError>,
+    F: FnOnce(A) -> Result<T, E>,
+{
+    try_load_puzzle_file(&puzzle_test_path(year, day, test_number), parser)
+}
+
 // Thank you Francis Gagné! : https://stackoverflow.com/a/42356713
 pub trait SliceExt {
     type Item;
@@ -67,6 +349,154 @@ pub trait Grid {
     fn last_cell_coord(&self) -> Point;
 
     fn get_row(&self, y: isize) -> Option<&[Self::Item]>;
+
+    /// Every cell with its coordinates, row by row from [`Grid::first_cell_coord`]
+    ///
+    /// The default looks up every point with [`Grid::get_cell`]; the grids here walk
+    /// their storage instead.
+    fn enumerate_cells(&self) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        GridCoordinateIter::new(self.first_cell_coord(), self.last_cell_coord())
+            .filter_map(|p| self.get_cell(p.x, p.y).map(|cell| (p, cell)))
+    }
+
+    /// Like [`Grid::enumerate_cells`], with every cell mutable
+    ///
+    /// Required: [`Grid::get_cell_mut`] can't hand out more than one cell at a time, so
+    /// only the grid itself can. This is a breaking change for grids implemented
+    /// outside this crate, they have to add it.
+    fn enumerate_cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut Self::Item)>
+    where
+        Self: Sized;
+
+    /// The coordinates of every cell matching `predicate`, row by row
+    fn positions<F: FnMut(&Self::Item) -> bool>(
+        &self,
+        mut predicate: F,
+    ) -> impl Iterator<Item = Point>
+    where
+        Self: Sized,
+    {
+        self.enumerate_cells()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    /// The coordinates of the first cell matching `predicate`, row by row
+    fn find<F: FnMut(&Self::Item) -> bool>(&self, predicate: F) -> Option<Point>
+    where
+        Self: Sized,
+    {
+        self.positions(predicate).next()
+    }
+
+    /// The cells around `p` in `neighborhood` that are on the grid, clockwise from north
+    fn neighbors(
+        &self,
+        p: Point,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        neighborhood
+            .directions()
+            .iter()
+            .filter_map(move |direction| {
+                let q = direction.step(p);
+                self.get_cell(q.x, q.y).map(|cell| (q, cell))
+            })
+    }
+
+    /// The up to 4 cells sharing an edge with `p`
+    fn neighbors4(&self, p: Point) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.neighbors(p, Neighborhood::Four)
+    }
+
+    /// The up to 8 cells sharing an edge or a corner with `p`
+    fn neighbors8(&self, p: Point) -> impl Iterator<Item = (Point, &Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.neighbors(p, Neighborhood::Eight)
+    }
+
+    /// Like [`Grid::neighbors`], with every neighbor mutable
+    ///
+    /// The default picks the neighbors out of [`Grid::enumerate_cells_mut`], walking
+    /// the whole grid; the grids here borrow just the neighbors' cells.
+    fn neighbors_mut(
+        &mut self,
+        p: Point,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (Point, &mut Self::Item)>
+    where
+        Self: Sized,
+    {
+        let around: Vec<Point> = neighborhood
+            .directions()
+            .iter()
+            .map(|direction| direction.step(p))
+            .collect();
+        let mut found: Vec<(usize, Point, &mut Self::Item)> = self
+            .enumerate_cells_mut()
+            .filter_map(|(q, cell)| Some((around.iter().position(|r| *r == q)?, q, cell)))
+            .collect();
+        found.sort_by_key(|(order, ..)| *order);
+        found.into_iter().map(|(_, q, cell)| (q, cell))
+    }
+
+    /// Like [`Grid::neighbors4`], with every neighbor mutable
+    fn neighbors4_mut(&mut self, p: Point) -> impl Iterator<Item = (Point, &mut Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.neighbors_mut(p, Neighborhood::Four)
+    }
+
+    /// Like [`Grid::neighbors8`], with every neighbor mutable
+    fn neighbors8_mut(&mut self, p: Point) -> impl Iterator<Item = (Point, &mut Self::Item)>
+    where
+        Self: Sized,
+    {
+        self.neighbors_mut(p, Neighborhood::Eight)
+    }
+}
+
+/// Mutable references to the elements at `indices`, which must be increasing and in bounds
+fn disjoint_mut<T>(mut rest: &mut [T], indices: impl IntoIterator<Item = usize>) -> Vec<&mut T> {
+    let mut offset = 0;
+    let mut found = vec![];
+    for ndx in indices {
+        let (item, tail) = std::mem::take(&mut rest)[ndx - offset..]
+            .split_first_mut()
+            .expect("indices must be increasing and in bounds");
+        found.push(item);
+        rest = tail;
+        offset = ndx + 1;
+    }
+    found
+}
+
+/// Puts neighbors borrowed in storage order back in clockwise order
+///
+/// `around` holds each neighbor's place clockwise from north and its point, in the
+/// same order as `cells`.
+fn clockwise<'a, T>(
+    around: impl Iterator<Item = (usize, Point)>,
+    cells: Vec<&'a mut T>,
+) -> impl Iterator<Item = (Point, &'a mut T)> {
+    let mut found: Vec<(usize, Point, &'a mut T)> = around
+        .zip(cells)
+        .map(|((order, q), cell)| (order, q, cell))
+        .collect();
+    found.sort_by_key(|(order, ..)| *order);
+    found.into_iter().map(|(_, q, cell)| (q, cell))
 }
 
 pub trait Growable {
@@ -159,6 +589,79 @@ where
     }
 }
 
+impl<T> StaticGrid<T> {
+    /// Builds a grid from text, one row per line, mapping every character with its position
+    ///
+    /// Panics if the rows aren't all the same width, see [`StaticGrid::try_parse`].
+    pub fn parse(text: &str, mut cell: impl FnMut(char, Point) -> T) -> Self {
+        Self::try_parse(text, |c, p| Ok::<T, String>(cell(c, p))).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Like [`StaticGrid::parse`], reporting ragged rows and the mapper's errors instead
+    /// of panicking
+    ///
+    /// Trailing newlines are ignored. A row whose width differs from the first row's is
+    /// reported at its line, with the column where it should have ended.
+    pub fn try_parse<E: Display>(
+        text: &str,
+        cell: impl FnMut(char, Point) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let rows = text.trim_end_matches(['\n', '\r']).lines().map(str::chars);
+        Self::try_from_rows(rows, cell)
+    }
+
+    /// The one grid builder behind [`StaticGrid::try_parse`] and [`bytes::grid`],
+    /// taking each row as an iterator over its cells
+    ///
+    /// [`bytes::grid`]: crate::bytes::grid
+    pub(crate) fn try_from_rows<C, R, E>(
+        rows: impl Iterator<Item = R>,
+        mut cell: impl FnMut(C, Point) -> Result<T, E>,
+    ) -> Result<Self, ParseError>
+    where
+        R: Iterator<Item = C> + Clone,
+        E: Display,
+    {
+        let mut cells = vec![];
+        let mut num_rows = 0;
+        let mut num_cols = 0;
+        for (y, row) in rows.enumerate() {
+            let width = row.clone().count();
+            if y == 0 {
+                num_cols = width;
+            } else if width != num_cols {
+                return Err(ParseError::new(
+                    y + 1,
+                    width.min(num_cols) + 1,
+                    format!("row is {width} wide, the first row is {num_cols}"),
+                ));
+            }
+            for (x, c) in row.enumerate() {
+                let point = Point::new(x as isize, y as isize);
+                cells.push(
+                    cell(c, point).map_err(|e| ParseError::new(y + 1, x + 1, e.to_string()))?,
+                );
+            }
+            num_rows += 1;
+        }
+        Ok(StaticGrid {
+            cells,
+            num_rows,
+            num_cols,
+        })
+    }
+
+    /// The grid as text, one line per row, the inverse of [`StaticGrid::parse`]
+    pub fn to_text(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity(self.num_rows * (self.num_cols + 1));
+        for row in self.cells.chunks(self.num_cols.max(1)) {
+            text.extend(row.iter().map(&mut cell));
+            text.push('\n');
+        }
+        text
+    }
+}
+
 impl<T> Grid for StaticGrid<T>
 where
     T: Default + Clone,
@@ -190,6 +693,41 @@ where
         Point::new(self.num_cols as isize - 1, self.num_rows as isize - 1)
     }
 
+    fn enumerate_cells(&self) -> impl Iterator<Item = (Point, &Self::Item)> {
+        let num_cols = self.num_cols;
+        self.cells.iter().enumerate().map(move |(ndx, cell)| {
+            let p = Point::new((ndx % num_cols) as isize, (ndx / num_cols) as isize);
+            (p, cell)
+        })
+    }
+
+    fn enumerate_cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut Self::Item)> {
+        let num_cols = self.num_cols;
+        self.cells.iter_mut().enumerate().map(move |(ndx, cell)| {
+            let p = Point::new((ndx % num_cols) as isize, (ndx / num_cols) as isize);
+            (p, cell)
+        })
+    }
+
+    fn neighbors_mut(
+        &mut self,
+        p: Point,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (Point, &mut Self::Item)> {
+        // (index into `cells`, clockwise order, point), by index to split the borrows
+        let mut around: Vec<(usize, usize, Point)> = neighborhood
+            .directions()
+            .iter()
+            .map(|direction| direction.step(p))
+            .enumerate()
+            .filter(|(_, q)| self.get_cell(q.x, q.y).is_some())
+            .map(|(order, q)| (q.y as usize * self.num_cols + q.x as usize, order, q))
+            .collect();
+        around.sort_by_key(|(ndx, ..)| *ndx);
+        let cells = disjoint_mut(&mut self.cells, around.iter().map(|(ndx, ..)| *ndx));
+        clockwise(around.into_iter().map(|(_, order, q)| (order, q)), cells)
+    }
+
     fn get_row(&self, y: isize) -> Option<&[Self::Item]> {
         if y >= self.num_rows as isize || y < 0 {
             None
@@ -231,11 +769,53 @@ pub enum CardinalDirection {
     NorthWest,
 }
 
+impl CardinalDirection {
+    /// The point one step from `p` in this direction, north being -y
+    pub fn step(&self, p: Point) -> Point {
+        let (dx, dy) = match self {
+            CardinalDirection::North => (0, -1),
+            CardinalDirection::NorthEast => (1, -1),
+            CardinalDirection::East => (1, 0),
+            CardinalDirection::SouthEast => (1, 1),
+            CardinalDirection::South => (0, 1),
+            CardinalDirection::SouthWest => (-1, 1),
+            CardinalDirection::West => (-1, 0),
+            CardinalDirection::NorthWest => (-1, -1),
+        };
+        Point::new(p.x + dx, p.y + dy)
+    }
+}
+
 pub enum ClockDirection {
     Clockwise,
     CounterClockwise,
 }
 
+/// Which cells count as next to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Sharing an edge
+    Four,
+    /// Sharing an edge or a corner
+    Eight,
+    /// Sharing an edge within a row, like the digits of a number
+    Horizontal,
+}
+
+impl Neighborhood {
+    /// The directions to the neighbors, clockwise from north
+    pub fn directions(self) -> &'static [CardinalDirection] {
+        use CardinalDirection::*;
+        match self {
+            Neighborhood::Four => &[North, East, South, West],
+            Neighborhood::Eight => &[
+                North, NorthEast, East, SouthEast, South, SouthWest, West, NorthWest,
+            ],
+            Neighborhood::Horizontal => &[East, West],
+        }
+    }
+}
+
 /*
     Structs
 */
@@ -511,18 +1091,15 @@ impl Iterator for GridCoordinateIter {
     type Item = Point;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let this_point = Point {
-            x: self.next_point.x,
-            y: self.next_point.y,
-        };
+        if self.next_point.y > self.end.y || self.start.x > self.end.x {
+            return None;
+        }
+        let this_point = self.next_point;
         self.next_point.x = this_point.x + 1;
         if self.next_point.x > self.end.x {
             self.next_point.y += 1;
             self.next_point.x = self.start.x;
         }
-        if self.next_point.y > self.end.y {
-            return None;
-        }
         Some(this_point)
     }
 }
@@ -712,6 +1289,66 @@ where
         )
     }
 
+    fn enumerate_cells(&self) -> impl Iterator<Item = (Point, &Self::Item)> {
+        let first = self.first_cell_coord();
+        self.cells.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter().enumerate().map(move |(x, cell)| {
+                let p = Point::new(first.x + x as isize, first.y + y as isize);
+                (p, cell)
+            })
+        })
+    }
+
+    fn enumerate_cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut Self::Item)> {
+        let first = self.first_cell_coord();
+        self.cells.iter_mut().enumerate().flat_map(move |(y, row)| {
+            row.iter_mut().enumerate().map(move |(x, cell)| {
+                let p = Point::new(first.x + x as isize, first.y + y as isize);
+                (p, cell)
+            })
+        })
+    }
+
+    fn neighbors_mut(
+        &mut self,
+        p: Point,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (Point, &mut Self::Item)> {
+        // ((row, column) indices, clockwise order, point), sorted to split the rows,
+        // then each row's cells
+        let mut around: Vec<((usize, usize), usize, Point)> = neighborhood
+            .directions()
+            .iter()
+            .map(|direction| direction.step(p))
+            .enumerate()
+            .filter_map(|(order, q)| {
+                let (local_x, local_y) = self.translate_absolute_to_local(q.x, q.y);
+                let (ndx_x, ndx_y) = self.translate_local_to_indices(local_x, local_y);
+                self.cell_exists(ndx_x, ndx_y).then_some((
+                    (ndx_y as usize, ndx_x as usize),
+                    order,
+                    q,
+                ))
+            })
+            .collect();
+        around.sort_by_key(|(ndx, ..)| *ndx);
+        let mut row_ndxs: Vec<usize> = around.iter().map(|((y, _), ..)| *y).collect();
+        row_ndxs.dedup();
+        let rows = disjoint_mut(&mut self.cells, row_ndxs.iter().copied());
+        let cells: Vec<&mut CellType> = row_ndxs
+            .iter()
+            .zip(rows)
+            .flat_map(|(&row_ndx, row)| {
+                let cols = around
+                    .iter()
+                    .filter(move |((y, _), ..)| *y == row_ndx)
+                    .map(|((_, x), ..)| *x);
+                disjoint_mut(row, cols)
+            })
+            .collect();
+        clockwise(around.into_iter().map(|(_, order, q)| (order, q)), cells)
+    }
+
     fn get_row(&self, y: isize) -> Option<&[Self::Item]> {
         let y_ndx = self
             .translate_local_to_indices(0, self.translate_absolute_to_local(0, y).1)
@@ -798,7 +1435,7 @@ pub struct Point2D<CoordType> {
     pub y: CoordType,
 }
 
-#[derive(Clone, Default, Debug, Copy)]
+#[derive(Clone, Default, Debug, Copy, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: isize,
     pub y: isize,
@@ -812,9 +1449,14 @@ impl Point {
 
 #[cfg(test)]
 mod tests {
-    use std::fmt::Display;
+    use std::{env, fmt::Display, fs};
 
-    use crate::{DynamicGrid, Growable, StaticGrid};
+    use crate::{
+        find_puzzle_dir_from, load_puzzle_test, parse_puzzle, puzzle_dir, resolve_puzzle_dir,
+        try_load_puzzle_file, try_load_puzzle_test, year_dir, DynamicGrid, Grid,
+        GridCoordinateIter, Growable, Input, Neighborhood, ParseError, Point, PuzzleError,
+        StaticGrid, DEFAULT_YEAR,
+    };
 
     /*
     Test Structs
@@ -836,6 +1478,37 @@ mod tests {
         }
     }
 
+    /// A grid with only the required methods, to test the trait's defaults
+    struct Wrapped(StaticGrid<char>);
+
+    impl Grid for Wrapped {
+        type Item = char;
+
+        fn get_cell(&self, x: isize, y: isize) -> Option<&char> {
+            self.0.get_cell(x, y)
+        }
+
+        fn get_cell_mut(&mut self, x: isize, y: isize) -> Option<&mut char> {
+            self.0.get_cell_mut(x, y)
+        }
+
+        fn first_cell_coord(&self) -> Point {
+            self.0.first_cell_coord()
+        }
+
+        fn last_cell_coord(&self) -> Point {
+            self.0.last_cell_coord()
+        }
+
+        fn get_row(&self, y: isize) -> Option<&[char]> {
+            self.0.get_row(y)
+        }
+
+        fn enumerate_cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut char)> {
+            self.0.enumerate_cells_mut()
+        }
+    }
+
     #[test]
     fn test_grid() {
         let mut g: StaticGrid<TestCell> = StaticGrid::new(2, 2);
@@ -852,6 +1525,153 @@ mod tests {
         assert_eq!(i.next().unwrap().value, 'a');
     }
 
+    #[test]
+    fn test_parse_grid() {
+        let text = "#.#\n..S\n";
+        let g = StaticGrid::parse(text, |c, p| (c, p.x + p.y));
+        assert_eq!((g.num_rows, g.num_cols), (2, 3));
+        assert_eq!(g.get_cell(2, 1), Some(&('S', 3)));
+        assert_eq!(g.to_text(|(c, _)| *c), text);
+
+        let err = StaticGrid::try_parse("...\n..\n...", |c, _| Ok::<char, String>(c)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = StaticGrid::try_parse("12\n3x", |c, _| c.to_digit(10).ok_or("not a digit"))
+            .unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, "not a digit"));
+        assert_eq!(StaticGrid::parse("", |c, _| c).cells, []);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let mut g = StaticGrid::parse("abc\ndef\n", |c, _| c);
+        let around =
+            |g: &StaticGrid<char>, p| -> String { g.neighbors8(p).map(|(_, c)| *c).collect() };
+        assert_eq!(around(&g, Point::new(1, 0)), "cfeda");
+        assert_eq!(
+            g.neighbors4(Point::new(0, 1)).collect::<Vec<_>>(),
+            [(Point::new(0, 0), &'a'), (Point::new(1, 1), &'e')]
+        );
+
+        for (_, c) in g.neighbors4_mut(Point::new(1, 1)) {
+            *c = c.to_ascii_uppercase();
+        }
+        assert_eq!(g.to_text(|c| *c), "aBc\nDeF\n");
+        // Clockwise from north, like `neighbors`
+        let order: String = g
+            .neighbors8_mut(Point::new(1, 0))
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(order, "cFeDa");
+        assert_eq!(
+            g.neighbors_mut(Point::new(2, 1), Neighborhood::Four)
+                .count(),
+            2
+        );
+
+        // Absolute coordinates, the grid grows to 500..=501 x 0..=1
+        let mut d = DynamicGrid::<TestCell>::new(500, 0);
+        d.get_cell_or_add_mut(501, 1).value = 'S';
+        let points: Vec<Point> = d.neighbors8(Point::new(500, 0)).map(|(p, _)| p).collect();
+        assert_eq!(
+            points,
+            [Point::new(501, 0), Point::new(501, 1), Point::new(500, 1)]
+        );
+        let points: Vec<Point> = d
+            .neighbors4_mut(Point::new(500, 1))
+            .map(|(q, cell)| {
+                cell.value = 'X';
+                q
+            })
+            .collect();
+        assert_eq!(points, [Point::new(500, 0), Point::new(501, 1)]);
+        assert_eq!(d.to_string(), "X.\n.X\n");
+
+        // The default, for grids that don't borrow their neighbors themselves
+        let mut w = Wrapped(StaticGrid::parse("abc\ndef\n", |c, _| c));
+        let order: String = w
+            .neighbors8_mut(Point::new(1, 0))
+            .map(|(_, c)| {
+                *c = c.to_ascii_uppercase();
+                *c
+            })
+            .collect();
+        assert_eq!(order, "CFEDA");
+        assert_eq!(w.0.to_text(|c| *c), "AbC\nDEF\n");
+    }
+
+    #[test]
+    fn test_grid_coordinate_iter() {
+        // Row by row, up to and including `end`
+        assert_eq!(
+            GridCoordinateIter::new(Point::new(-1, 5), Point::new(0, 6)).collect::<Vec<_>>(),
+            [
+                Point::new(-1, 5),
+                Point::new(0, 5),
+                Point::new(-1, 6),
+                Point::new(0, 6)
+            ]
+        );
+        assert_eq!(
+            GridCoordinateIter::new(Point::new(2, 2), Point::new(2, 2)).collect::<Vec<_>>(),
+            [Point::new(2, 2)]
+        );
+        // An `end` before `start` in either direction is empty
+        assert_eq!(
+            GridCoordinateIter::new(Point::new(0, 0), Point::new(-1, 3)).count(),
+            0
+        );
+        assert_eq!(
+            GridCoordinateIter::new(Point::new(0, 0), Point::new(3, -1)).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_enumerate_cells() {
+        let mut g = StaticGrid::parse("#.\n.#\n", |c, _| c);
+        assert_eq!(
+            g.enumerate_cells().collect::<Vec<_>>(),
+            [
+                (Point::new(0, 0), &'#'),
+                (Point::new(1, 0), &'.'),
+                (Point::new(0, 1), &'.'),
+                (Point::new(1, 1), &'#')
+            ]
+        );
+        assert_eq!(
+            g.positions(|c| *c == '#').collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(1, 1)]
+        );
+        for (p, c) in g.enumerate_cells_mut() {
+            if p.x == p.y {
+                *c = 'x';
+            }
+        }
+        assert_eq!(g.find(|c| *c == 'x'), Some(Point::new(0, 0)));
+        assert_eq!(g.find(|c| *c == '#'), None);
+
+        // Nothing for an empty grid
+        assert_eq!(StaticGrid::<char>::default().enumerate_cells().count(), 0);
+
+        // The default, for grids that don't walk their own storage
+        let w = Wrapped(g.clone());
+        assert_eq!(
+            w.enumerate_cells().collect::<Vec<_>>(),
+            g.enumerate_cells().collect::<Vec<_>>()
+        );
+
+        // Absolute coordinates, starting from the top left
+        let mut d = DynamicGrid::<TestCell>::new(500, 0);
+        d.get_cell_or_add_mut(499, -1).value = 'S';
+        d.get_cell_or_add_mut(500, 0);
+        assert_eq!(d.enumerate_cells().count(), 4);
+        assert_eq!(d.find(|c| c.value == 'S'), Some(Point::new(499, -1)));
+        for (_, cell) in d.enumerate_cells_mut() {
+            cell.value = 'X';
+        }
+        assert_eq!(d.positions(|c| c.value == 'X').count(), 4);
+    }
+
     #[test]
     fn test_dynamic_grid() {
         let mut g = DynamicGrid::<TestCell>::new(500, 0);
@@ -902,4 +1722,76 @@ mod tests {
         }
         assert_eq!(s, "*....W");
     }
+
+    #[test]
+    fn test_try_load_missing() {
+        let err = try_load_puzzle_test(DEFAULT_YEAR, 99, 1, Ok::<String, ParseError>).unwrap_err();
+        match err {
+            PuzzleError::MissingFile { path } => {
+                assert!(path.is_absolute());
+                assert!(path.ends_with("puzzles/2023/day99_test1.txt"));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_at_offset() {
+        let input = "ab\ncé\nd";
+        let e = ParseError::at_offset(input, 4, "");
+        assert_eq!((e.line, e.column), (2, 2));
+        // Inside the two bytes of é
+        let e = ParseError::at_offset(input, 5, "");
+        assert_eq!((e.line, e.column), (2, 2));
+        let e = ParseError::at_offset(input, 100, "");
+        assert_eq!((e.line, e.column), (3, 2));
+    }
+
+    #[test]
+    fn test_try_load_invalid_utf8() {
+        let path = env::temp_dir().join("aoc_utils_invalid_utf8.txt");
+        fs::write(&path, b"12\n3\xff4\n").unwrap();
+        let err = try_load_puzzle_file(&path, Ok::<String, ParseError>).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(err, PuzzleError::InvalidUtf8 { offset: 4, .. }));
+    }
+
+    #[test]
+    fn test_try_load_parse_error() {
+        let path = env::temp_dir().join("aoc_utils_parse_error.txt");
+        fs::write(&path, "1\n22\nx3\n").unwrap();
+        let err = try_load_puzzle_file(&path, |s: String| {
+            s.lines()
+                .map(|l| l.parse::<u32>())
+                .collect::<Result<Vec<u32>, _>>()
+                .map_err(|e| ParseError::at_offset(&s, s.find('x').unwrap(), e.to_string()))
+        })
+        .unwrap_err();
+        fs::remove_file(&path).unwrap();
+        match err {
+            PuzzleError::Parse { line, column, .. } => assert_eq!((line, column), (3, 1)),
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_puzzle_dir() {
+        let dir = puzzle_dir();
+        assert!(dir.ends_with("puzzles"));
+        assert!(year_dir(DEFAULT_YEAR).join("day1_test1.txt").is_file());
+
+        let nested = dir.parent().unwrap().join("utils").join("src");
+        assert_eq!(find_puzzle_dir_from(&nested), Some(dir));
+
+        let overridden = env::temp_dir().join("elsewhere");
+        assert_eq!(resolve_puzzle_dir(Some(overridden.clone())), overridden);
+    }
+
+    #[test]
+    fn test_include_puzzle() {
+        let embedded = parse_puzzle(crate::include_puzzle_test!(1, 2), |s: Input| s);
+        let loaded = load_puzzle_test(DEFAULT_YEAR, 1, 2, |s: Input| s);
+        assert_eq!(embedded, loaded);
+        assert_eq!(embedded.lines().count(), 7);
+    }
 }
@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use crate::runner::{DayRun, Failure};
+
+/// How answers are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// `Solution 1: label: answer`, for people
+    #[default]
+    Text,
+    /// One JSON object per line, see [`Record::to_json`]
+    Json,
+    /// A header line, then one row per answer, see [`Record::to_csv`]
+    Csv,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// One answer with what it took to get it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// [`input_hash`] of the puzzle text, empty when the input was streamed
+    pub input_hash: String,
+    /// Why there is no answer, when the part was run isolated and failed
+    pub failure: Option<Failure>,
+}
+
+impl Record {
+    pub const CSV_HEADER: &'static str =
+        "year,day,part,answer,parse_ns,solve_ns,input_hash,status,message";
+
+    pub fn from_run(run: &DayRun) -> Vec<Record> {
+        run.parts
+            .iter()
+            .map(|part_run| Record {
+                year: run.year,
+                day: run.day,
+                part: part_run.part,
+                answer: part_run.answer.clone(),
+                parse_time: run.parse_time,
+                solve_time: part_run.time,
+                input_hash: run.input_hash.clone(),
+                failure: part_run.failure.clone(),
+            })
+            .collect()
+    }
+
+    /// `ok`, or the [`Failure::status`]
+    pub fn status(&self) -> &'static str {
+        self.failure.as_ref().map_or("ok", Failure::status)
+    }
+
+    /// `{"year":2023,"day":3,"part":1,"answer":"4361",..,"status":"ok","message":""}`
+    ///
+    /// The answer is always a string, whatever the day's answer type was.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":\"{}\",\"parse_ns\":{},\"solve_ns\":{},\"input_hash\":\"{}\",\"status\":\"{}\",\"message\":\"{}\"}}",
+            self.year,
+            self.day,
+            self.part,
+            json_escape(&self.answer),
+            self.parse_time.as_nanos(),
+            self.solve_time.as_nanos(),
+            json_escape(&self.input_hash),
+            self.status(),
+            json_escape(&self.message()),
+        )
+    }
+
+    /// A row matching [`CSV_HEADER`](Record::CSV_HEADER), quoting the answer if needed
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            self.year,
+            self.day,
+            self.part,
+            csv_field(&self.answer),
+            self.parse_time.as_nanos(),
+            self.solve_time.as_nanos(),
+            csv_field(&self.input_hash),
+            self.status(),
+            csv_field(&self.message()),
+        )
+    }
+
+    fn message(&self) -> String {
+        self.failure
+            .as_ref()
+            .map_or_else(String::new, Failure::message)
+    }
+}
+
+/// 64-bit FNV-1a of the puzzle bytes as 16 hex digits
+///
+/// Enough to tell inputs apart on a dashboard, not meant to be cryptographic.
+pub fn input_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::report::{input_hash, Record};
+    use crate::runner::Failure;
+
+    fn record(answer: &str) -> Record {
+        Record {
+            year: 2023,
+            day: 3,
+            part: 1,
+            answer: answer.to_string(),
+            parse_time: Duration::from_micros(12),
+            solve_time: Duration::from_nanos(340),
+            input_hash: input_hash(b"467..114.."),
+            failure: None,
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            record("4361").to_json(),
+            format!(
+                "{{\"year\":2023,\"day\":3,\"part\":1,\"answer\":\"4361\",\"parse_ns\":12000,\"solve_ns\":340,\"input_hash\":\"{}\",\"status\":\"ok\",\"message\":\"\"}}",
+                input_hash(b"467..114..")
+            )
+        );
+        assert!(record("elf \"3\"\n")
+            .to_json()
+            .contains(r#""answer":"elf \"3\"\n""#));
+    }
+
+    #[test]
+    fn test_csv() {
+        assert!(record("4361")
+            .to_csv()
+            .starts_with("2023,3,1,4361,12000,340,"));
+        assert!(record("a,\"b\"")
+            .to_csv()
+            .starts_with("2023,3,1,\"a,\"\"b\"\"\",12000"));
+
+        let mut failed = record("");
+        failed.failure = Some(Failure::Panicked("index 3, len 2".to_string()));
+        assert!(failed.to_csv().ends_with(",panicked,\"index 3, len 2\""));
+        assert!(failed
+            .to_json()
+            .ends_with(r#""status":"panicked","message":"index 3, len 2"}"#));
+    }
+
+    #[test]
+    fn test_input_hash() {
+        // Published FNV-1a test vectors
+        assert_eq!(input_hash(b""), "cbf29ce484222325");
+        assert_eq!(input_hash(b"a"), "af63dc4c8601ec8c");
+    }
+}
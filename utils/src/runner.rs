@@ -0,0 +1,388 @@
+use std::{
+    any::Any,
+    fmt::{self, Display},
+    hint::black_box,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    puzzle_path, read_puzzle,
+    report::input_hash,
+    timing::{DayTimings, Phase},
+    Input, PuzzleError,
+};
+
+/// One day's puzzle: how to parse it and how to solve both parts
+///
+/// Implemented by a unit struct in each day crate (`pub struct Day3;`) so the
+/// `aoc` runner can drive every day the same way.
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+
+    type Input;
+
+    fn parse(input: Input) -> Self::Input;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+/// Why an isolated part has no answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The parse or the part panicked, with the panic message
+    Panicked(String),
+    /// Still running when the timeout ran out
+    TimedOut(Duration),
+}
+
+impl Failure {
+    /// `panicked` or `timeout`, as reports name it
+    pub fn status(&self) -> &'static str {
+        match self {
+            Failure::Panicked(_) => "panicked",
+            Failure::TimedOut(_) => "timeout",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Failure::Panicked(message) => message.clone(),
+            Failure::TimedOut(timeout) => format!("still running after {timeout:?}"),
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "PANICKED {message}"),
+            Failure::TimedOut(timeout) => write!(f, "TIMED OUT after {timeout:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: u32,
+    /// Empty when the part failed
+    pub answer: String,
+    /// Time spent solving, not counting the parse
+    pub time: Duration,
+    /// Only an isolated run catches failures, see [`run_isolated`]
+    pub failure: Option<Failure>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRun {
+    pub year: u32,
+    pub day: u32,
+    /// See [`input_hash`](crate::report::input_hash)
+    pub input_hash: String,
+    pub parse_time: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Parses `text` and solves the selected part, or both when `part` is `None`
+pub fn run_solution<S: Solution>(text: String, part: Option<u32>) -> DayRun {
+    let input_hash = input_hash(text.as_bytes());
+    let start = Instant::now();
+    let input = S::parse(Input::from(text));
+    let parse_time = start.elapsed();
+
+    let mut parts = vec![];
+    if part.is_none_or(|p| p == 1) {
+        let start = Instant::now();
+        let answer = S::part1(&input).to_string();
+        parts.push(PartRun {
+            part: 1,
+            answer,
+            time: start.elapsed(),
+            failure: None,
+        });
+    }
+    if part.is_none_or(|p| p == 2) {
+        let start = Instant::now();
+        let answer = S::part2(&input).to_string();
+        parts.push(PartRun {
+            part: 2,
+            answer,
+            time: start.elapsed(),
+            failure: None,
+        });
+    }
+    DayRun {
+        year: S::YEAR,
+        day: S::DAY,
+        input_hash,
+        parse_time,
+        parts,
+    }
+}
+
+/// Like [`run_solution`], but each part parses and solves on a thread of its own
+///
+/// A panic in either step is caught and recorded as that part's [`Failure`]
+/// instead of unwinding into the caller, and so is a part still running after
+/// `timeout`. A thread can't be stopped from outside, so a timed out part is left
+/// running in the background until the process exits. `parse_time` is the first
+/// part's that got through its parse.
+pub fn run_isolated<S: Solution + 'static>(
+    text: String,
+    part: Option<u32>,
+    timeout: Duration,
+) -> DayRun {
+    let input_hash = input_hash(text.as_bytes());
+    let mut parse_time = None;
+    let mut parts = vec![];
+    for part in [1, 2]
+        .into_iter()
+        .filter(|p| part.is_none_or(|only| only == *p))
+    {
+        let text = text.clone();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let start = Instant::now();
+                let input = S::parse(Input::from(text));
+                let parse_time = start.elapsed();
+                let start = Instant::now();
+                let answer = if part == 1 {
+                    S::part1(&input).to_string()
+                } else {
+                    S::part2(&input).to_string()
+                };
+                (parse_time, answer, start.elapsed())
+            }));
+            // Nobody is listening any more if the part timed out
+            let _ = sender.send(result.map_err(panic_message));
+        });
+        let (answer, time, failure) = match receiver.recv_timeout(timeout) {
+            Ok(Ok((part_parse_time, answer, time))) => {
+                parse_time.get_or_insert(part_parse_time);
+                (answer, time, None)
+            }
+            Ok(Err(message)) => (
+                String::new(),
+                Duration::ZERO,
+                Some(Failure::Panicked(message)),
+            ),
+            Err(_) => (String::new(), timeout, Some(Failure::TimedOut(timeout))),
+        };
+        parts.push(PartRun {
+            part,
+            answer,
+            time,
+            failure,
+        });
+    }
+    DayRun {
+        year: S::YEAR,
+        day: S::DAY,
+        input_hash,
+        parse_time: parse_time.unwrap_or_default(),
+        parts,
+    }
+}
+
+/// The message `panic!` was given, when it was a string
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked without a message".to_string()
+    }
+}
+
+/// Parses and solves `text` `iterations` times, timing each phase separately
+///
+/// Every iteration parses a fresh copy of the input. The parse phase covers the same
+/// span as [`run_solution`]'s `parse_time`, normalizing the text included.
+pub fn time_solution<S: Solution>(text: String, iterations: u32) -> DayTimings {
+    fn timed<T>(samples: &mut Vec<Duration>, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = black_box(f());
+        samples.push(start.elapsed());
+        result
+    }
+
+    let mut samples = [vec![], vec![], vec![]];
+    for _ in 0..iterations {
+        let text = text.clone();
+        let input = timed(&mut samples[0], || S::parse(Input::from(black_box(text))));
+        timed(&mut samples[1], || S::part1(&input));
+        timed(&mut samples[2], || S::part2(&input));
+    }
+    DayTimings {
+        year: S::YEAR,
+        day: S::DAY,
+        samples: Phase::ALL.into_iter().zip(samples).collect(),
+    }
+}
+
+/// A [`Solution`] with its types erased, so days can share one list
+#[derive(Debug, Clone, Copy)]
+pub struct Registered {
+    pub year: u32,
+    pub day: u32,
+    run: fn(String, Option<u32>) -> DayRun,
+    run_isolated: fn(String, Option<u32>, Duration) -> DayRun,
+    time: fn(String, u32) -> DayTimings,
+}
+
+impl Registered {
+    pub const fn of<S: Solution + 'static>() -> Registered {
+        Registered {
+            year: S::YEAR,
+            day: S::DAY,
+            run: run_solution::<S>,
+            run_isolated: run_isolated::<S>,
+            time: time_solution::<S>,
+        }
+    }
+
+    pub fn run(&self, text: String, part: Option<u32>) -> DayRun {
+        (self.run)(text, part)
+    }
+
+    /// Runs against the day's own `puzzles/{year}/day{day}.txt`
+    pub fn run_puzzle(&self, part: Option<u32>) -> Result<DayRun, PuzzleError> {
+        let text = read_puzzle(&puzzle_path(self.year, self.day))?;
+        Ok(self.run(text, part))
+    }
+
+    /// See [`run_isolated`]
+    pub fn run_isolated(&self, text: String, part: Option<u32>, timeout: Duration) -> DayRun {
+        (self.run_isolated)(text, part, timeout)
+    }
+
+    /// Runs isolated against the day's own `puzzles/{year}/day{day}.txt`
+    pub fn run_puzzle_isolated(
+        &self,
+        part: Option<u32>,
+        timeout: Duration,
+    ) -> Result<DayRun, PuzzleError> {
+        let text = read_puzzle(&puzzle_path(self.year, self.day))?;
+        Ok(self.run_isolated(text, part, timeout))
+    }
+
+    pub fn time(&self, text: String, iterations: u32) -> DayTimings {
+        (self.time)(text, iterations)
+    }
+
+    /// Times the day against its own `puzzles/{year}/day{day}.txt`
+    pub fn time_puzzle(&self, iterations: u32) -> Result<DayTimings, PuzzleError> {
+        let text = read_puzzle(&puzzle_path(self.year, self.day))?;
+        Ok(self.time(text, iterations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Display, thread, time::Duration};
+
+    use crate::runner::{Failure, Registered};
+    use crate::{Input, Solution};
+
+    struct Sums;
+
+    impl Solution for Sums {
+        const YEAR: u32 = 1999;
+        const DAY: u32 = 99;
+
+        type Input = Vec<i64>;
+
+        fn parse(input: Input) -> Self::Input {
+            input.ints().collect()
+        }
+
+        fn part1(input: &Self::Input) -> impl Display {
+            input.iter().sum::<i64>()
+        }
+
+        fn part2(input: &Self::Input) -> impl Display {
+            format!("max {}", input.iter().max().unwrap())
+        }
+    }
+
+    #[test]
+    fn test_registered() {
+        const SUMS: Registered = Registered::of::<Sums>();
+        assert_eq!((SUMS.year, SUMS.day), (1999, 99));
+
+        let run = SUMS.run("1 2\n-3 10\n".to_string(), None);
+        let answers: Vec<(u32, &str)> = run
+            .parts
+            .iter()
+            .map(|p| (p.part, p.answer.as_str()))
+            .collect();
+        assert_eq!(answers, [(1, "10"), (2, "max 10")]);
+        assert_eq!(run.input_hash.len(), 16);
+        assert_eq!(SUMS.run("5".to_string(), Some(2)).parts.len(), 1);
+        assert!(SUMS.run_puzzle(None).is_err());
+    }
+
+    /// Part 1 panics on an empty input, part 2 never finishes on a `loop` one
+    struct Fragile;
+
+    impl Solution for Fragile {
+        const YEAR: u32 = 1999;
+        const DAY: u32 = 98;
+
+        type Input = String;
+
+        fn parse(input: Input) -> Self::Input {
+            input.to_string()
+        }
+
+        fn part1(input: &Self::Input) -> impl Display {
+            input.lines().next().unwrap().len()
+        }
+
+        fn part2(input: &Self::Input) -> impl Display {
+            if input == "loop" {
+                loop {
+                    thread::sleep(Duration::from_millis(10));
+                }
+            }
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_run_isolated() {
+        const FRAGILE: Registered = Registered::of::<Fragile>();
+        let timeout = Duration::from_millis(200);
+
+        let run = FRAGILE.run_isolated("abc".to_string(), None, timeout);
+        assert_eq!(run.parts[0].answer, "3");
+        assert!(run.parts.iter().all(|p| p.failure.is_none()));
+
+        let run = FRAGILE.run_isolated(String::new(), Some(1), timeout);
+        assert_eq!(run.parts.len(), 1);
+        assert_eq!(run.parts[0].answer, "");
+        assert!(matches!(
+            &run.parts[0].failure,
+            Some(Failure::Panicked(message)) if message.contains("unwrap")
+        ));
+
+        let run = FRAGILE.run_isolated("loop".to_string(), None, timeout);
+        assert_eq!(run.parts[0].answer, "4");
+        assert_eq!(run.parts[1].failure, Some(Failure::TimedOut(timeout)));
+    }
+
+    #[test]
+    fn test_time() {
+        const SUMS: Registered = Registered::of::<Sums>();
+        let timings = SUMS.time("1 2 3".to_string(), 5);
+        assert_eq!(timings.day, 99);
+        assert_eq!(timings.samples.len(), 3);
+        assert!(timings.samples.iter().all(|(_, s)| s.len() == 5));
+        assert_eq!(timings.stats().len(), 3);
+    }
+}
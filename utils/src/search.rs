@@ -0,0 +1,537 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use crate::{Grid, Neighborhood, Point, StaticGrid};
+
+/// The cheapest way found from a source to a target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    /// Sum of the step costs, the number of steps for a breadth-first search
+    pub cost: u64,
+    /// From the source to the target, both included
+    pub points: Vec<Point>,
+}
+
+/// Steps from the nearest of `sources` to every cell, `None` where there's no way there
+///
+/// Only cells for which `passable` holds are entered; the sources themselves always
+/// count, and those off the grid are ignored. Cell (0, 0) of the map is the grid's
+/// [`Grid::first_cell_coord`], so for a [`StaticGrid`] the coordinates are the same.
+pub fn bfs<G, P>(
+    grid: &G,
+    sources: impl IntoIterator<Item = Point>,
+    neighborhood: Neighborhood,
+    passable: P,
+) -> StaticGrid<Option<u64>>
+where
+    G: Grid,
+    P: FnMut(&G::Item) -> bool,
+{
+    let mut search = Search::new(grid, neighborhood, sources, []);
+    search.breadth_first(passable);
+    search.distances()
+}
+
+/// The fewest steps from any of `sources` to the nearest of `targets`, see [`bfs`]
+pub fn bfs_path<G, P>(
+    grid: &G,
+    sources: impl IntoIterator<Item = Point>,
+    targets: impl IntoIterator<Item = Point>,
+    neighborhood: Neighborhood,
+    passable: P,
+) -> Option<Path>
+where
+    G: Grid,
+    P: FnMut(&G::Item) -> bool,
+{
+    let mut search = Search::new(grid, neighborhood, sources, targets);
+    let found = search.breadth_first(passable)?;
+    Some(search.path(found))
+}
+
+/// Cheapest cost from the nearest of `sources` to every cell, like [`bfs`] but with
+/// `cost(from, to)` for each step
+pub fn dijkstra<G, P, C>(
+    grid: &G,
+    sources: impl IntoIterator<Item = Point>,
+    neighborhood: Neighborhood,
+    passable: P,
+    cost: C,
+) -> StaticGrid<Option<u64>>
+where
+    G: Grid,
+    P: FnMut(&G::Item) -> bool,
+    C: FnMut(&G::Item, &G::Item) -> u64,
+{
+    let mut search = Search::new(grid, neighborhood, sources, []);
+    search.best_first(passable, cost, |_| 0);
+    search.distances()
+}
+
+/// The cheapest way from any of `sources` to the nearest of `targets`, see [`dijkstra`]
+pub fn dijkstra_path<G, P, C>(
+    grid: &G,
+    sources: impl IntoIterator<Item = Point>,
+    targets: impl IntoIterator<Item = Point>,
+    neighborhood: Neighborhood,
+    passable: P,
+    cost: C,
+) -> Option<Path>
+where
+    G: Grid,
+    P: FnMut(&G::Item) -> bool,
+    C: FnMut(&G::Item, &G::Item) -> u64,
+{
+    astar(grid, sources, targets, neighborhood, passable, cost, |_| 0)
+}
+
+/// [`dijkstra_path`] guided by `heuristic`, an estimate of the cost left from a point
+///
+/// The path is only the cheapest if the heuristic never overestimates, [`manhattan`]
+/// to the target does for 4-connected steps costing at least 1.
+pub fn astar<G, P, C, H>(
+    grid: &G,
+    sources: impl IntoIterator<Item = Point>,
+    targets: impl IntoIterator<Item = Point>,
+    neighborhood: Neighborhood,
+    passable: P,
+    cost: C,
+    heuristic: H,
+) -> Option<Path>
+where
+    G: Grid,
+    P: FnMut(&G::Item) -> bool,
+    C: FnMut(&G::Item, &G::Item) -> u64,
+    H: FnMut(Point) -> u64,
+{
+    let mut search = Search::new(grid, neighborhood, sources, targets);
+    let found = search.best_first(passable, cost, heuristic)?;
+    Some(search.path(found))
+}
+
+/// Steps between `a` and `b` moving only along rows and columns
+pub fn manhattan(a: Point, b: Point) -> u64 {
+    a.x.abs_diff(b.x) as u64 + a.y.abs_diff(b.y) as u64
+}
+
+/// The cells matching `predicate` that connect to `start` through matching cells,
+/// row by row
+///
+/// Empty if `start` itself doesn't match or is off the grid.
+pub fn flood_fill<G, P>(
+    grid: &G,
+    start: Point,
+    neighborhood: Neighborhood,
+    mut predicate: P,
+) -> Vec<Point>
+where
+    G: Grid,
+    P: FnMut(&G::Item) -> bool,
+{
+    if !grid.get_cell(start.x, start.y).is_some_and(&mut predicate) {
+        return vec![];
+    }
+    let mut search = Search::new(grid, neighborhood, [start], []);
+    search.breadth_first(predicate);
+    (0..search.dist.len())
+        .filter(|ndx| search.dist[*ndx].is_some())
+        .map(|ndx| search.point(ndx))
+        .collect()
+}
+
+/// One group of connected cells found by [`label_components`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// Row by row, starting from the top left cell
+    pub cells: Vec<Point>,
+    /// Smallest x and y of the cells
+    pub top_left: Point,
+    /// Largest x and y of the cells
+    pub bottom_right: Point,
+}
+
+impl Component {
+    /// Number of cells
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Every component of a grid, with a label for each cell
+#[derive(Debug, Clone)]
+pub struct Components {
+    /// The index into `components` of each cell's component, `None` for the cells
+    /// that didn't match; indexed like the maps of [`bfs`]
+    pub labels: StaticGrid<Option<usize>>,
+    /// Ordered by their first cell, row by row
+    pub components: Vec<Component>,
+    origin: Point,
+}
+
+impl Components {
+    /// The label of the component holding `p`, in the grid's own coordinates
+    pub fn label(&self, p: Point) -> Option<usize> {
+        *self
+            .labels
+            .get_cell(p.x - self.origin.x, p.y - self.origin.y)?
+    }
+
+    /// The component holding `p`, in the grid's own coordinates
+    pub fn component(&self, p: Point) -> Option<&Component> {
+        self.label(p).map(|label| &self.components[label])
+    }
+}
+
+/// Splits the cells matching `predicate` into groups connected through `neighborhood`
+pub fn label_components<G, P>(grid: &G, neighborhood: Neighborhood, mut predicate: P) -> Components
+where
+    G: Grid,
+    P: FnMut(&G::Item) -> bool,
+{
+    let search = Search::new(grid, neighborhood, [], []);
+    let mut matches = vec![false; search.dist.len()];
+    for (p, cell) in grid.enumerate_cells() {
+        if predicate(cell) {
+            matches[search.index(p).unwrap()] = true;
+        }
+    }
+
+    let mut labels = vec![None; matches.len()];
+    let mut components = vec![];
+    for first in 0..matches.len() {
+        if !matches[first] || labels[first].is_some() {
+            continue;
+        }
+        let label = components.len();
+        labels[first] = Some(label);
+        let mut found = vec![first];
+        let mut ndx = 0;
+        while let Some(&at) = found.get(ndx) {
+            for (q, _) in grid.neighbors(search.point(at), neighborhood) {
+                let next = search.index(q).unwrap();
+                if matches[next] && labels[next].is_none() {
+                    labels[next] = Some(label);
+                    found.push(next);
+                }
+            }
+            ndx += 1;
+        }
+        found.sort_unstable();
+
+        let cells: Vec<Point> = found.into_iter().map(|ndx| search.point(ndx)).collect();
+        let top_left = Point::new(cells.iter().map(|p| p.x).min().unwrap(), cells[0].y);
+        let bottom_right = Point::new(
+            cells.iter().map(|p| p.x).max().unwrap(),
+            cells[cells.len() - 1].y,
+        );
+        components.push(Component {
+            cells,
+            top_left,
+            bottom_right,
+        });
+    }
+
+    Components {
+        labels: StaticGrid {
+            cells: labels,
+            num_rows: search.num_rows,
+            num_cols: search.num_cols,
+        },
+        components,
+        origin: search.origin,
+    }
+}
+
+/// Distances and back links for every cell of the grid's bounding box, by index
+struct Search<'a, G> {
+    grid: &'a G,
+    neighborhood: Neighborhood,
+    origin: Point,
+    num_rows: usize,
+    num_cols: usize,
+    sources: Vec<usize>,
+    targets: Vec<bool>,
+    dist: Vec<Option<u64>>,
+    prev: Vec<Option<usize>>,
+}
+
+impl<'a, G: Grid> Search<'a, G> {
+    fn new(
+        grid: &'a G,
+        neighborhood: Neighborhood,
+        sources: impl IntoIterator<Item = Point>,
+        targets: impl IntoIterator<Item = Point>,
+    ) -> Self {
+        let origin = grid.first_cell_coord();
+        let last = grid.last_cell_coord();
+        let num_cols = (last.x - origin.x + 1).max(0) as usize;
+        let num_rows = (last.y - origin.y + 1).max(0) as usize;
+        let mut search = Search {
+            grid,
+            neighborhood,
+            origin,
+            num_rows,
+            num_cols,
+            sources: vec![],
+            targets: vec![false; num_rows * num_cols],
+            dist: vec![None; num_rows * num_cols],
+            prev: vec![None; num_rows * num_cols],
+        };
+        for p in sources {
+            if let Some(ndx) = search.index(p) {
+                search.sources.push(ndx);
+                search.dist[ndx] = Some(0);
+            }
+        }
+        for p in targets {
+            if let Some(ndx) = search.index(p) {
+                search.targets[ndx] = true;
+            }
+        }
+        search
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        self.grid.get_cell(p.x, p.y)?;
+        let x = (p.x - self.origin.x) as usize;
+        let y = (p.y - self.origin.y) as usize;
+        Some(y * self.num_cols + x)
+    }
+
+    fn point(&self, ndx: usize) -> Point {
+        Point::new(
+            self.origin.x + (ndx % self.num_cols) as isize,
+            self.origin.y + (ndx / self.num_cols) as isize,
+        )
+    }
+
+    /// Fills in the distances in unit steps, returning the first target reached
+    fn breadth_first<P: FnMut(&G::Item) -> bool>(&mut self, mut passable: P) -> Option<usize> {
+        let mut queue: VecDeque<usize> = self.sources.iter().copied().collect();
+        while let Some(ndx) = queue.pop_front() {
+            if self.targets[ndx] {
+                return Some(ndx);
+            }
+            let steps = self.dist[ndx].unwrap() + 1;
+            for (q, cell) in self.grid.neighbors(self.point(ndx), self.neighborhood) {
+                let next = self.index(q).unwrap();
+                if self.dist[next].is_none() && passable(cell) {
+                    self.dist[next] = Some(steps);
+                    self.prev[next] = Some(ndx);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Fills in the cheapest costs, cheapest estimated total first, returning the first
+    /// target reached
+    fn best_first<P, C, H>(
+        &mut self,
+        mut passable: P,
+        mut cost: C,
+        mut heuristic: H,
+    ) -> Option<usize>
+    where
+        P: FnMut(&G::Item) -> bool,
+        C: FnMut(&G::Item, &G::Item) -> u64,
+        H: FnMut(Point) -> u64,
+    {
+        let mut heap = BinaryHeap::new();
+        for &ndx in &self.sources {
+            heap.push(Reverse((heuristic(self.point(ndx)), 0, ndx)));
+        }
+        while let Some(Reverse((_, so_far, ndx))) = heap.pop() {
+            if self.dist[ndx].is_some_and(|d| d < so_far) {
+                // Already reached more cheaply
+                continue;
+            }
+            if self.targets[ndx] {
+                return Some(ndx);
+            }
+            let p = self.point(ndx);
+            let from = self.grid.get_cell(p.x, p.y).unwrap();
+            for (q, cell) in self.grid.neighbors(p, self.neighborhood) {
+                let next = self.index(q).unwrap();
+                if !passable(cell) {
+                    continue;
+                }
+                let total = so_far + cost(from, cell);
+                if self.dist[next].is_none_or(|d| total < d) {
+                    self.dist[next] = Some(total);
+                    self.prev[next] = Some(ndx);
+                    heap.push(Reverse((total + heuristic(q), total, next)));
+                }
+            }
+        }
+        None
+    }
+
+    fn path(&self, target: usize) -> Path {
+        let mut points = vec![self.point(target)];
+        let mut at = target;
+        while let Some(prev) = self.prev[at] {
+            points.push(self.point(prev));
+            at = prev;
+        }
+        points.reverse();
+        Path {
+            cost: self.dist[target].unwrap(),
+            points,
+        }
+    }
+
+    fn distances(self) -> StaticGrid<Option<u64>> {
+        StaticGrid {
+            cells: self.dist,
+            num_rows: self.num_rows,
+            num_cols: self.num_cols,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::{
+        astar, bfs, bfs_path, dijkstra, dijkstra_path, flood_fill, label_components, manhattan,
+    };
+    use crate::{DynamicGrid, Grid, Growable, Neighborhood, Point, StaticGrid};
+
+    const MAZE: &str = "S.#.
+.##.
+...E
+";
+
+    #[test]
+    fn test_bfs() {
+        let maze = StaticGrid::parse(MAZE, |c, _| c);
+        let open = |c: &char| *c != '#';
+        let start = maze.find(|c| *c == 'S').unwrap();
+        let end = maze.find(|c| *c == 'E').unwrap();
+
+        let distances = bfs(&maze, [start], Neighborhood::Four, open);
+        let text =
+            distances.to_text(|d| d.map_or('#', |d| char::from_digit(d as u32, 10).unwrap()));
+        assert_eq!(text, "01#7\n1##6\n2345\n");
+
+        let path = bfs_path(&maze, [start], [end], Neighborhood::Four, open).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.points.first(), Some(&start));
+        assert_eq!(path.points.last(), Some(&end));
+        assert_eq!(path.points.len(), 6);
+
+        // Corners can be cut, and the nearest of several targets wins
+        let path = bfs_path(
+            &maze,
+            [start],
+            [end, Point::new(3, 0)],
+            Neighborhood::Eight,
+            open,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.points[1], Point::new(0, 1));
+        assert_eq!(path.points[2], Point::new(1, 2));
+
+        // Every cell measured from the closest source
+        let distances = bfs(&maze, [start, end], Neighborhood::Four, open);
+        assert_eq!(distances.get_cell(1, 2), Some(&Some(2)));
+        assert_eq!(distances.get_cell(3, 0), Some(&Some(2)));
+
+        let walled = StaticGrid::parse("S#E\n", |c, _| c);
+        let path = bfs_path(
+            &walled,
+            [Point::new(0, 0)],
+            [Point::new(2, 0)],
+            Neighborhood::Eight,
+            |c: &char| *c != '#',
+        );
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn test_weighted() {
+        // Entering a cell costs its digit
+        let city = StaticGrid::parse("1911\n1919\n1111\n", |c, _| c.to_digit(10).unwrap() as u64);
+        let start = Point::new(0, 0);
+        let end = Point::new(3, 0);
+        let cost = |_: &u64, to: &u64| *to;
+
+        let distances = dijkstra(&city, [start], Neighborhood::Four, |_| true, cost);
+        assert_eq!(distances.get_cell(3, 0), Some(&Some(7)));
+        assert_eq!(distances.get_cell(1, 0), Some(&Some(9)));
+
+        let cheapest =
+            dijkstra_path(&city, [start], [end], Neighborhood::Four, |_| true, cost).unwrap();
+        assert_eq!(cheapest.cost, 7);
+        assert_eq!(cheapest.points.len(), 8);
+
+        let guided = astar(
+            &city,
+            [start],
+            [end],
+            Neighborhood::Four,
+            |_| true,
+            cost,
+            |p| manhattan(p, end),
+        )
+        .unwrap();
+        assert_eq!(guided.cost, cheapest.cost);
+
+        // A grid with absolute coordinates maps its first cell to (0, 0)
+        let mut d = DynamicGrid::<u64>::new(10, 10);
+        *d.get_cell_or_add_mut(12, 11) = 1;
+        let distances = dijkstra(
+            &d,
+            [Point::new(10, 10)],
+            Neighborhood::Eight,
+            |_| true,
+            |_, _| 2,
+        );
+        assert_eq!(distances.get_cell(2, 1), Some(&Some(4)));
+    }
+
+    #[test]
+    fn test_components() {
+        let plot = StaticGrid::parse("AA.B\n.A.B\nB..A\n", |c, _| c);
+        let letter = |c: &char| *c != '.';
+
+        assert_eq!(
+            flood_fill(&plot, Point::new(0, 0), Neighborhood::Four, letter),
+            [Point::new(0, 0), Point::new(1, 0), Point::new(1, 1)]
+        );
+        // The B in the corner touches the As diagonally
+        assert_eq!(
+            flood_fill(&plot, Point::new(0, 2), Neighborhood::Eight, letter).len(),
+            4
+        );
+        assert!(flood_fill(&plot, Point::new(2, 0), Neighborhood::Four, letter).is_empty());
+
+        let components = label_components(&plot, Neighborhood::Four, letter);
+        assert_eq!(components.components.len(), 3);
+        let text = components
+            .labels
+            .to_text(|l| l.map_or('.', |l| char::from_digit(l as u32, 10).unwrap()));
+        assert_eq!(text, "00.1\n.0.1\n2..1\n");
+        let first = &components.components[0];
+        assert_eq!(first.size(), 3);
+        assert_eq!(
+            (first.top_left, first.bottom_right),
+            (Point::new(0, 0), Point::new(1, 1))
+        );
+
+        // Within rows only, and in the grid's own coordinates
+        let mut d = DynamicGrid::<u64>::new(-1, -1);
+        *d.get_cell_or_add_mut(0, 0) = 1;
+        *d.get_cell_or_add_mut(0, -1) = 1;
+        let components = label_components(&d, Neighborhood::Horizontal, |v| *v == 1);
+        assert_eq!(components.components.len(), 2);
+        assert_eq!(components.label(Point::new(0, 0)), Some(1));
+        assert_eq!(components.component(Point::new(0, -1)).unwrap().size(), 1);
+        assert_eq!(components.label(Point::new(-1, -1)), None);
+        assert_eq!(components.label(Point::new(5, 5)), None);
+    }
+}
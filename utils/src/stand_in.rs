@@ -0,0 +1,78 @@
+//! A stand-in for the puzzle server, for the fetch and submit tests
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+/// What the stand-in server was sent
+pub(crate) struct Request {
+    pub path: String,
+    /// Whether the request carried `session=secret`
+    pub authorized: bool,
+    pub body: String,
+}
+
+/// Serves every request with `respond`'s status and body, counting them
+///
+/// Returns the base URL to point a `FetchConfig` at and the request count.
+pub(crate) fn stand_in_server(
+    respond: impl Fn(&Request) -> (u16, String) + Send + 'static,
+) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut authorized = false;
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                let header = header.to_lowercase();
+                authorized |= header.starts_with("cookie: session=secret");
+                if let Some(len) = header.strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request = Request {
+                path: request_line
+                    .split(' ')
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string(),
+                authorized,
+                body: String::from_utf8(body).unwrap(),
+            };
+
+            let (status, body) = respond(&request);
+            let reason = match status {
+                200 => "OK",
+                404 => "Not Found",
+                _ => "Error",
+            };
+            let response = format!(
+                "HTTP/1.1 {status} {reason}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, hits)
+}
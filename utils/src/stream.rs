@@ -0,0 +1,188 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use memmap2::Mmap;
+
+use crate::{absolute_path, puzzle_path, PuzzleError};
+
+/// The lines of a puzzle, read one at a time instead of all at once
+///
+/// Line endings (`\n` or `\r\n`) are stripped. If reading fails part way through,
+/// or a line isn't UTF-8, the error is the last item.
+pub struct PuzzleLines {
+    reader: Box<dyn BufRead>,
+    path: PathBuf,
+    /// Bytes read so far, to locate invalid UTF-8
+    offset: usize,
+    failed: bool,
+}
+
+impl PuzzleLines {
+    pub fn new(reader: Box<dyn BufRead>, path: &Path) -> PuzzleLines {
+        PuzzleLines {
+            reader,
+            path: path.to_path_buf(),
+            offset: 0,
+            failed: false,
+        }
+    }
+
+    pub fn open(puzzle_path: &Path) -> Result<PuzzleLines, PuzzleError> {
+        let file = File::open(puzzle_path).map_err(|e| PuzzleError::from_io(puzzle_path, e))?;
+        Ok(PuzzleLines::new(
+            Box::new(BufReader::new(file)),
+            &absolute_path(puzzle_path),
+        ))
+    }
+}
+
+impl Iterator for PuzzleLines {
+    type Item = Result<String, PuzzleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let mut line = vec![];
+        let read = match self.reader.read_until(b'\n', &mut line) {
+            Ok(0) => return None,
+            Ok(read) => read,
+            Err(e) => {
+                self.failed = true;
+                return Some(Err(PuzzleError::from_io(&self.path, e)));
+            }
+        };
+        while line.last().is_some_and(|b| matches!(b, b'\n' | b'\r')) {
+            line.pop();
+        }
+        let line = String::from_utf8(line).map_err(|e| {
+            self.failed = true;
+            PuzzleError::InvalidUtf8 {
+                path: self.path.clone(),
+                offset: self.offset + e.utf8_error().valid_up_to(),
+            }
+        });
+        self.offset += read;
+        Some(line)
+    }
+}
+
+/// Puzzle bytes that are mapped straight from the file where possible
+pub enum PuzzleBytes {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+    Static(&'static [u8]),
+}
+
+impl PuzzleBytes {
+    /// Maps the file into memory rather than reading it
+    pub fn map(puzzle_path: &Path) -> Result<PuzzleBytes, PuzzleError> {
+        let file = File::open(puzzle_path).map_err(|e| PuzzleError::from_io(puzzle_path, e))?;
+        let len = file
+            .metadata()
+            .map_err(|e| PuzzleError::from_io(puzzle_path, e))?
+            .len();
+        if len == 0 {
+            // Not every platform can map an empty file
+            return Ok(PuzzleBytes::Owned(vec![]));
+        }
+        // Safety: puzzle inputs aren't modified while a day is running. If one is,
+        // the parser sees odd bytes, which it has to cope with anyway.
+        let map = unsafe { Mmap::map(&file) }.map_err(|e| PuzzleError::from_io(puzzle_path, e))?;
+        Ok(PuzzleBytes::Mapped(map))
+    }
+
+    pub fn read<R: io::Read>(mut reader: R, path: &Path) -> Result<PuzzleBytes, PuzzleError> {
+        let mut bytes = vec![];
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|e| PuzzleError::from_io(path, e))?;
+        Ok(PuzzleBytes::Owned(bytes))
+    }
+}
+
+impl Deref for PuzzleBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            PuzzleBytes::Mapped(map) => map,
+            PuzzleBytes::Owned(bytes) => bytes,
+            PuzzleBytes::Static(bytes) => bytes,
+        }
+    }
+}
+
+/// Hands the parser the day's input one line at a time, if the file can be opened
+pub fn try_load_puzzle_lines<T, F: FnOnce(PuzzleLines) -> T>(
+    year: u32,
+    day: u32,
+    parser: F,
+) -> Result<T, PuzzleError> {
+    Ok(parser(PuzzleLines::open(&puzzle_path(year, day))?))
+}
+
+/// Hands the parser the day's input as bytes mapped from the file, if it can be opened
+pub fn try_load_puzzle_mapped<T, F: FnOnce(&[u8]) -> T>(
+    year: u32,
+    day: u32,
+    parser: F,
+) -> Result<T, PuzzleError> {
+    Ok(parser(&PuzzleBytes::map(&puzzle_path(year, day))?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, io::Cursor, path::Path};
+
+    use crate::{
+        load_puzzle_test, try_load_puzzle_lines, Input, PuzzleBytes, PuzzleError, PuzzleLines,
+        DEFAULT_YEAR,
+    };
+
+    #[test]
+    fn test_lines_match_input() {
+        let streamed: Vec<String> = PuzzleLines::open(&crate::puzzle_test_path(DEFAULT_YEAR, 2, 1))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let loaded = load_puzzle_test(DEFAULT_YEAR, 2, 1, |s: Input| {
+            s.lines().map(str::to_string).collect::<Vec<_>>()
+        });
+        assert_eq!(streamed, loaded);
+
+        let crlf = PuzzleLines::new(Box::new(Cursor::new("a\r\nb\n\nc")), Path::new("-"));
+        assert_eq!(
+            crlf.collect::<Result<Vec<_>, _>>().unwrap(),
+            ["a", "b", "", "c"]
+        );
+
+        // The error ends the lines instead of panicking
+        let bad = PuzzleLines::new(Box::new(Cursor::new(b"ab\nc\xffd\ne\n")), Path::new("-"));
+        let lines: Vec<_> = bad.collect();
+        assert_eq!(lines.len(), 2);
+        assert!(matches!(
+            lines[1],
+            Err(PuzzleError::InvalidUtf8 { offset: 4, .. })
+        ));
+
+        let missing = try_load_puzzle_lines(DEFAULT_YEAR, 99, |lines| lines.count());
+        assert!(matches!(missing, Err(PuzzleError::MissingFile { .. })));
+    }
+
+    #[test]
+    fn test_mapped_bytes() {
+        let path = crate::puzzle_test_path(DEFAULT_YEAR, 3, 1);
+        let mapped = PuzzleBytes::map(&path).unwrap();
+        assert_eq!(&*mapped, fs::read(&path).unwrap().as_slice());
+
+        let empty = env::temp_dir().join("aoc_utils_empty_map.txt");
+        fs::write(&empty, b"").unwrap();
+        assert!(PuzzleBytes::map(&empty).unwrap().is_empty());
+        fs::remove_file(&empty).unwrap();
+    }
+}
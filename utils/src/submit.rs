@@ -0,0 +1,583 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    absolute_path,
+    fetch::{new_agent, FetchConfig, FetchError, Throttle},
+    puzzle_dir, read_puzzle, PuzzleError,
+};
+
+/// File in each year's puzzle directory that keeps every guess that got a verdict
+const HISTORY_FILE: &str = "guesses";
+
+/// What the server made of a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way
+    Wrong,
+    /// Nothing was judged, try again after `wait`
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part was already solved, or isn't unlocked yet
+    WrongLevel,
+    /// A page the parser didn't recognize, with its text
+    Unknown(String),
+}
+
+impl Outcome {
+    /// Reads the verdict out of the page the server answers a submission with
+    pub fn parse(html: &str) -> Outcome {
+        let text = html_text(html);
+        if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if text.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else if text.contains("You gave an answer too recently") {
+            Outcome::RateLimited {
+                wait: parse_wait(&text).unwrap_or(Duration::from_secs(60)),
+            }
+        } else if text.contains("solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown(text)
+        }
+    }
+
+    /// Name used in the history file, `None` for outcomes that aren't a verdict
+    fn history_name(&self) -> Option<&'static str> {
+        match self {
+            Outcome::Correct => Some("correct"),
+            Outcome::TooHigh => Some("too_high"),
+            Outcome::TooLow => Some("too_low"),
+            Outcome::Wrong => Some("wrong"),
+            _ => None,
+        }
+    }
+
+    fn from_history_name(name: &str) -> Option<Outcome> {
+        [
+            Outcome::Correct,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Wrong,
+        ]
+        .into_iter()
+        .find(|o| o.history_name() == Some(name))
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited { wait } => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            Outcome::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            Outcome::Unknown(text) => write!(f, "unrecognized response: {text}"),
+        }
+    }
+}
+
+/// `You have 1m 5s left to wait`, or `You have 30s left to wait`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let rest = &text[text.find("You have ")? + 9..];
+    let rest = &rest[..rest.find(" left to wait")?];
+    let mut secs = 0;
+    for part in rest.split_whitespace() {
+        let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let n: u64 = n.parse().ok()?;
+        secs += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// The `<article>` text of a response page, tags stripped and whitespace collapsed
+fn html_text(html: &str) -> String {
+    let body = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::with_capacity(body.len());
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// One judged guess
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Why a guess wasn't sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part is already solved with this answer
+    AlreadyCorrect { answer: String },
+    /// Exactly this answer was already judged wrong
+    KnownWrong { outcome: Outcome },
+    /// Earlier feedback rules the answer out; the bounds are exclusive
+    OutOfBounds {
+        above: Option<i64>,
+        below: Option<i64>,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect { answer } => {
+                write!(f, "already solved, the answer was {answer}")
+            }
+            Refusal::KnownWrong { outcome } => {
+                write!(f, "already guessed, it was {outcome}")
+            }
+            Refusal::OutOfBounds { above, below } => {
+                let above = above.map_or("..".to_string(), |n| n.to_string());
+                let below = below.map_or("..".to_string(), |n| n.to_string());
+                write!(
+                    f,
+                    "earlier guesses put the answer between {above} and {below}"
+                )
+            }
+        }
+    }
+}
+
+/// Every judged guess for one year, kept in `puzzles/{year}/guesses`
+///
+/// One `day part outcome answer` line per guess, oldest first; the answer is the
+/// rest of the line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuessHistory {
+    pub guesses: Vec<Guess>,
+}
+
+impl GuessHistory {
+    /// A missing file is an empty history
+    pub fn load_from(path: &Path) -> Result<GuessHistory, PuzzleError> {
+        let text = match read_puzzle(path) {
+            Ok(text) => text,
+            Err(PuzzleError::MissingFile { .. }) => return Ok(GuessHistory::default()),
+            Err(e) => return Err(e),
+        };
+        let mut guesses = vec![];
+        for (ndx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            guesses.push(parse_guess(line).ok_or_else(|| PuzzleError::Parse {
+                path: absolute_path(path),
+                line: ndx + 1,
+                column: 1,
+                message: "expected `day part outcome answer`".to_string(),
+            })?);
+        }
+        Ok(GuessHistory { guesses })
+    }
+
+    /// Creates the parent directory if needed, a new year has none yet
+    pub fn save_to(&self, path: &Path) -> Result<(), PuzzleError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| PuzzleError::from_io(dir, e))?;
+        }
+        fs::write(path, self.to_string()).map_err(|e| PuzzleError::from_io(path, e))
+    }
+
+    /// The guesses for one part, oldest first
+    pub fn for_part(&self, day: u32, part: u32) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |g| g.day == day && g.part == part)
+    }
+
+    /// The exclusive range earlier `too low` / `too high` verdicts leave open
+    pub fn bounds(&self, day: u32, part: u32) -> (Option<i64>, Option<i64>) {
+        let numeric = |outcome: Outcome| {
+            self.for_part(day, part)
+                .filter(move |g| g.outcome == outcome)
+                .filter_map(|g| g.answer.parse::<i64>().ok())
+        };
+        (
+            numeric(Outcome::TooLow).max(),
+            numeric(Outcome::TooHigh).min(),
+        )
+    }
+
+    /// Whether `answer` is worth sending, given what the server already said
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Result<(), Refusal> {
+        if let Some(correct) = self
+            .for_part(day, part)
+            .find(|g| g.outcome == Outcome::Correct)
+        {
+            return Err(Refusal::AlreadyCorrect {
+                answer: correct.answer.clone(),
+            });
+        }
+        if let Some(known) = self.for_part(day, part).find(|g| g.answer == answer) {
+            return Err(Refusal::KnownWrong {
+                outcome: known.outcome.clone(),
+            });
+        }
+        if let Ok(n) = answer.parse::<i64>() {
+            let (above, below) = self.bounds(day, part);
+            if above.is_some_and(|a| n <= a) || below.is_some_and(|b| n >= b) {
+                return Err(Refusal::OutOfBounds { above, below });
+            }
+        }
+        Ok(())
+    }
+
+    /// Remembers a verdict; rate limits and unrecognized pages aren't kept
+    pub fn record(&mut self, day: u32, part: u32, answer: &str, outcome: &Outcome) {
+        if outcome.history_name().is_some() {
+            self.guesses.push(Guess {
+                day,
+                part,
+                answer: answer.to_string(),
+                outcome: outcome.clone(),
+            });
+        }
+    }
+}
+
+/// `day part outcome answer`
+fn parse_guess(line: &str) -> Option<Guess> {
+    let mut fields = line.splitn(4, char::is_whitespace);
+    Some(Guess {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        outcome: Outcome::from_history_name(fields.next()?)?,
+        answer: fields.next()?.trim().to_string(),
+    })
+}
+
+impl Display for GuessHistory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day part outcome answer")?;
+        for guess in &self.guesses {
+            if let Some(name) = guess.outcome.history_name() {
+                writeln!(f, "{} {} {name} {}", guess.day, guess.part, guess.answer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// Not sent, the history already rules the answer out
+    Refused(Refusal),
+    /// The answer is empty or spans several lines
+    InvalidAnswer,
+    /// The guess history couldn't be read or written
+    History(PuzzleError),
+    Fetch(FetchError),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "not submitted, {refusal}"),
+            SubmitError::InvalidAnswer => write!(f, "the answer must be a single, non-empty line"),
+            SubmitError::History(e) => write!(f, "{e}"),
+            SubmitError::Fetch(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SubmitError::History(e) => Some(e),
+            SubmitError::Fetch(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FetchError> for SubmitError {
+    fn from(e: FetchError) -> Self {
+        SubmitError::Fetch(e)
+    }
+}
+
+/// Posts answers to `{base_url}/{year}/day/{day}/answer`, keeping a history of
+/// verdicts per year
+pub struct Submitter {
+    config: FetchConfig,
+    puzzle_dir: PathBuf,
+    agent: ureq::Agent,
+    throttle: Throttle,
+}
+
+impl Submitter {
+    pub fn new(config: FetchConfig) -> Submitter {
+        Submitter {
+            config,
+            puzzle_dir: puzzle_dir(),
+            agent: new_agent(),
+            throttle: Throttle::default(),
+        }
+    }
+
+    /// Keeps the histories under `dir` instead of the workspace `puzzles/`
+    pub fn with_puzzle_dir(mut self, dir: &Path) -> Submitter {
+        self.puzzle_dir = dir.to_path_buf();
+        self
+    }
+
+    fn history_path(&self, year: u32) -> PathBuf {
+        self.puzzle_dir.join(year.to_string()).join(HISTORY_FILE)
+    }
+
+    pub fn history(&self, year: u32) -> Result<GuessHistory, SubmitError> {
+        GuessHistory::load_from(&self.history_path(year)).map_err(SubmitError::History)
+    }
+
+    /// Submits `answer` unless the year's history already rules it out
+    ///
+    /// Every verdict is written to the history before returning. Requests are
+    /// spaced out by `min_interval`, sharing the marker [`Fetcher`] keeps.
+    ///
+    /// [`Fetcher`]: crate::fetch::Fetcher
+    pub fn submit(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> Result<Outcome, SubmitError> {
+        let answer = answer.trim();
+        if answer.is_empty() || answer.contains('\n') {
+            return Err(SubmitError::InvalidAnswer);
+        }
+        let mut history = self.history(year)?;
+        history
+            .check(day, part, answer)
+            .map_err(SubmitError::Refused)?;
+
+        let url = format!("{}/{year}/day/{day}/answer", self.config.base_url);
+        let transport = |e: ureq::Error| match e {
+            ureq::Error::StatusCode(status) => FetchError::Status {
+                url: url.clone(),
+                status,
+            },
+            e => FetchError::Transport {
+                url: url.clone(),
+                message: e.to_string(),
+            },
+        };
+        let level = part.to_string();
+        self.throttle
+            .wait_for_turn(&self.puzzle_dir, self.config.min_interval);
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.config.session))
+            .send_form([("level", level.as_str()), ("answer", answer)]);
+        self.throttle.mark_request(&self.puzzle_dir);
+        let html = response
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(transport)?;
+
+        let outcome = Outcome::parse(&html);
+        history.record(day, part, answer, &outcome);
+        history
+            .save_to(&self.history_path(year))
+            .map_err(SubmitError::History)?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    };
+
+    use crate::fetch::FetchConfig;
+    use crate::stand_in::{self, Request};
+    use crate::submit::{GuessHistory, Outcome, Refusal, SubmitError, Submitter};
+
+    fn page(message: &str) -> String {
+        format!("<html><main><article><p>{message}</p></article></main></html>")
+    }
+
+    /// Judges part 1 answers against 42, the way the real site words its verdicts
+    fn stand_in_server() -> (String, Arc<AtomicUsize>) {
+        stand_in::stand_in_server(|request: &Request| {
+            let answer: i64 = request
+                .body
+                .split('&')
+                .find_map(|kv| kv.strip_prefix("answer="))
+                .and_then(|a| a.parse().ok())
+                .unwrap_or(0);
+            let message = match answer.cmp(&42) {
+                std::cmp::Ordering::Equal => {
+                    "That's the right answer! You are one gold star closer."
+                }
+                std::cmp::Ordering::Less => "That's not the right answer; your answer is too low.",
+                std::cmp::Ordering::Greater => {
+                    "That's not the right answer; your answer is too high."
+                }
+            };
+            (200, page(message))
+        })
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse(&page("That's not the right answer.  If you're stuck, ...")),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait."
+            )),
+            Outcome::RateLimited {
+                wait: Duration::from_secs(65)
+            }
+        );
+        assert_eq!(
+            Outcome::parse(&page("You don't seem to be solving the right level.")),
+            Outcome::WrongLevel
+        );
+        assert!(matches!(Outcome::parse("<p>???</p>"), Outcome::Unknown(_)));
+    }
+
+    #[test]
+    fn test_history_checks() {
+        let mut history = GuessHistory::default();
+        history.record(5, 1, "100", &Outcome::TooHigh);
+        history.record(5, 1, "10", &Outcome::TooLow);
+        history.record(5, 1, "abc", &Outcome::Wrong);
+        history.record(
+            5,
+            1,
+            "50",
+            &Outcome::RateLimited {
+                wait: Duration::from_secs(5),
+            },
+        );
+        assert_eq!(history.guesses.len(), 3);
+
+        assert_eq!(history.check(5, 1, "50"), Ok(()));
+        assert_eq!(history.check(5, 2, "100"), Ok(()));
+        assert_eq!(
+            history.check(5, 1, "abc"),
+            Err(Refusal::KnownWrong {
+                outcome: Outcome::Wrong
+            })
+        );
+        assert_eq!(
+            history.check(5, 1, "150"),
+            Err(Refusal::OutOfBounds {
+                above: Some(10),
+                below: Some(100)
+            })
+        );
+        assert!(history.check(5, 1, "10").is_err());
+
+        history.record(5, 1, "42", &Outcome::Correct);
+        assert!(matches!(
+            history.check(5, 1, "43"),
+            Err(Refusal::AlreadyCorrect { .. })
+        ));
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, hits) = stand_in_server();
+        let dir = env::temp_dir().join(format!("aoc_submit_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut config = FetchConfig::new(&base_url, "secret");
+        config.min_interval = Duration::from_millis(100);
+        let mut submitter = Submitter::new(config).with_puzzle_dir(&dir);
+
+        assert_eq!(submitter.submit(2023, 1, 1, "10").unwrap(), Outcome::TooLow);
+        // The second answer waits its turn, like a fetch would
+        let start = Instant::now();
+        assert_eq!(
+            submitter.submit(2023, 1, 1, "90\n").unwrap(),
+            Outcome::TooHigh
+        );
+        assert!(start.elapsed() >= Duration::from_millis(75));
+        assert!(dir.join(".last_fetch").exists());
+        // Ruled out by the earlier verdicts, never sent
+        assert!(matches!(
+            submitter.submit(2023, 1, 1, "95"),
+            Err(SubmitError::Refused(Refusal::OutOfBounds { .. }))
+        ));
+        assert!(matches!(
+            submitter.submit(2023, 1, 1, "10"),
+            Err(SubmitError::Refused(Refusal::KnownWrong { .. }))
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        assert_eq!(
+            submitter.submit(2023, 1, 1, "42").unwrap(),
+            Outcome::Correct
+        );
+        let history = submitter.history(2023).unwrap();
+        assert_eq!(history.guesses.len(), 3);
+        assert_eq!(
+            GuessHistory::load_from(&dir.join("2023/guesses")).unwrap(),
+            history
+        );
+        // Another year keeps its own history
+        assert_eq!(submitter.history(2022).unwrap(), GuessHistory::default());
+        assert!(matches!(
+            submitter.submit(2023, 1, 1, ""),
+            Err(SubmitError::InvalidAnswer)
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+use std::{fmt, time::Duration};
+
+/// The separately timed steps of solving a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Phase> {
+        Phase::ALL.into_iter().find(|p| p.name() == name)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Summary of a set of timing samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// `None` for an empty set of samples
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = if secs.len() > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (secs.len() - 1) as f64
+        } else {
+            0.0
+        };
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Every sample taken for one day, per phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayTimings {
+    pub year: u32,
+    pub day: u32,
+    pub samples: Vec<(Phase, Vec<Duration>)>,
+}
+
+impl DayTimings {
+    pub fn stats(&self) -> Vec<(Phase, Stats)> {
+        self.samples
+            .iter()
+            .filter_map(|(phase, samples)| Some((*phase, Stats::from_samples(samples)?)))
+            .collect()
+    }
+}
+
+/// Median time per (year, day, phase) from an earlier benchmark run
+///
+/// Saved as one `year day phase nanoseconds` line per entry, so it can be diffed
+/// and edited by hand.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    pub entries: Vec<(u32, u32, Phase, Duration)>,
+}
+
+impl Baseline {
+    pub fn from_timings(timings: &[DayTimings]) -> Baseline {
+        Baseline {
+            entries: timings
+                .iter()
+                .flat_map(|t| {
+                    t.stats()
+                        .into_iter()
+                        .map(|(phase, stats)| (t.year, t.day, phase, stats.median))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, year: u32, day: u32, phase: Phase) -> Option<Duration> {
+        self.entries
+            .iter()
+            .find(|(y, d, p, _)| *y == year && *d == day && *p == phase)
+            .map(|(_, _, _, median)| *median)
+    }
+
+    /// Replaces any entries for the same (year, day, phase) with the ones in `other`
+    pub fn merge(&mut self, other: Baseline) {
+        self.entries
+            .retain(|(year, day, phase, _)| other.get(*year, *day, *phase).is_none());
+        self.entries.extend(other.entries);
+        self.entries
+            .sort_by_key(|(year, day, phase, _)| (*year, *day, *phase));
+    }
+
+    /// Reads the saved format; blank lines and `#` comments are skipped
+    pub fn parse(text: &str) -> Result<Baseline, String> {
+        let mut entries = vec![];
+        for (ndx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || format!("line {}: expected `year day phase nanoseconds`", ndx + 1);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [year, day, phase, nanos] = fields[..] else {
+                return Err(bad_line());
+            };
+            entries.push((
+                year.parse().map_err(|_| bad_line())?,
+                day.parse().map_err(|_| bad_line())?,
+                Phase::from_name(phase).ok_or_else(bad_line)?,
+                Duration::from_nanos(nanos.parse().map_err(|_| bad_line())?),
+            ));
+        }
+        Ok(Baseline { entries })
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# year day phase median_ns")?;
+        for (year, day, phase, median) in &self.entries {
+            writeln!(f, "{year} {day} {phase} {}", median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// How much slower (positive) or faster (negative) `now` is than `then`, in percent
+pub fn change_percent(then: Duration, now: Duration) -> f64 {
+    if then.is_zero() {
+        return 0.0;
+    }
+    (now.as_secs_f64() - then.as_secs_f64()) / then.as_secs_f64() * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::timing::{change_percent, Baseline, Phase, Stats};
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(6), ms(8)]).unwrap();
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.mean, ms(5));
+        // Sample stddev of 2, 4, 6, 8
+        assert_eq!(stats.stddev.as_micros(), 2581);
+
+        let single = Stats::from_samples(&[ms(3)]).unwrap();
+        assert_eq!((single.median, single.stddev), (ms(3), Duration::ZERO));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline {
+            entries: vec![
+                (2023, 3, Phase::Parse, ms(1)),
+                (2023, 3, Phase::Part2, ms(7)),
+            ],
+        };
+        assert_eq!(Baseline::parse(&baseline.to_string()), Ok(baseline.clone()));
+
+        baseline.merge(Baseline {
+            entries: vec![
+                (2023, 1, Phase::Part1, ms(2)),
+                (2023, 3, Phase::Parse, ms(5)),
+                (2022, 3, Phase::Parse, ms(9)),
+            ],
+        });
+        assert_eq!(
+            baseline.entries,
+            [
+                (2022, 3, Phase::Parse, ms(9)),
+                (2023, 1, Phase::Part1, ms(2)),
+                (2023, 3, Phase::Parse, ms(5)),
+                (2023, 3, Phase::Part2, ms(7))
+            ]
+        );
+        assert!(Baseline::parse("2023 3 solve 100").is_err());
+        assert!(Baseline::parse("3 parse 100").is_err());
+        assert_eq!(change_percent(ms(10), ms(12)).round(), 20.0);
+    }
+}
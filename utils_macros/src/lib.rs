@@ -0,0 +1,209 @@
+use std::{env, fs, path::PathBuf};
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse::Parser, punctuated::Punctuated, Error, Expr, Lit, LitInt, MetaNameValue, Token};
+
+use utils_manifest::read_manifest;
+
+/// Same as `utils::DEFAULT_YEAR`
+const DEFAULT_YEAR: u32 = 2023;
+
+/// Expands `puzzles/{year}/day{day}_tests.toml` into one `#[test]` per (example, part)
+///
+/// ```ignore
+/// utils::example_tests!(
+///     year = 2023, // optional, the default year otherwise
+///     day = 4,
+///     parser = parser,
+///     part1 = |cards| solve(&cards),
+///     part2 = |cards| solve2(&cards),
+/// );
+/// ```
+///
+/// Each part closure gets its own freshly parsed copy of the example, and its
+/// result is compared to the manifest's answer through `to_string()`.
+#[proc_macro]
+pub fn example_tests(input: TokenStream) -> TokenStream {
+    match expand(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Embeds `puzzles/{year}/day{day}.txt` into the binary, failing the build if it is missing
+///
+/// `include_puzzle!(2022, 4)`, or `include_puzzle!(4)` for the default year. The
+/// puzzle directory is found the same way `utils::puzzle_dir` does, from the crate
+/// invoking the macro.
+#[proc_macro]
+pub fn include_puzzle(input: TokenStream) -> TokenStream {
+    expand_include(input.into(), 1).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+/// Embeds `puzzles/{year}/day{day}_test{n}.txt`, as `include_puzzle_test!([year,] day, n)`
+#[proc_macro]
+pub fn include_puzzle_test(input: TokenStream) -> TokenStream {
+    expand_include(input.into(), 2).unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+/// `numbers` literals after an optional leading year
+fn expand_include(input: proc_macro2::TokenStream, numbers: usize) -> syn::Result<TokenStream> {
+    let args = Punctuated::<LitInt, Token![,]>::parse_terminated.parse2(input)?;
+    let mut values = args
+        .iter()
+        .map(LitInt::base10_parse::<u32>)
+        .collect::<syn::Result<Vec<u32>>>()?;
+    if values.len() == numbers {
+        values.insert(0, DEFAULT_YEAR);
+    }
+    let file_name = match values[..] {
+        [_, day] if numbers == 1 => format!("day{day}.txt"),
+        [_, day, n] if numbers == 2 => format!("day{day}_test{n}.txt"),
+        _ => {
+            let expected = if numbers == 1 {
+                "[year,] day"
+            } else {
+                "[year,] day, test number"
+            };
+            return Err(Error::new(
+                Span::call_site(),
+                format!("expected {expected}"),
+            ));
+        }
+    };
+    let path = puzzle_dir()
+        .join(values[0].to_string())
+        .join(file_name)
+        .to_string_lossy()
+        .to_string();
+    Ok(quote!(include_str!(#path)).into())
+}
+
+struct Args {
+    year: u32,
+    day: u32,
+    parser: Expr,
+    parts: [Option<Expr>; 2],
+}
+
+fn parse_args(input: proc_macro2::TokenStream) -> syn::Result<Args> {
+    let mut year = None;
+    let mut day = None;
+    let mut parser = None;
+    let mut parts = [None, None];
+    let pairs = Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse2(input)?;
+    for pair in pairs {
+        let key = pair.path.get_ident().map(ToString::to_string);
+        match key.as_deref() {
+            Some(name @ ("year" | "day")) => {
+                let value = match &pair.value {
+                    Expr::Lit(lit) => match &lit.lit {
+                        Lit::Int(n) => n.base10_parse()?,
+                        other => {
+                            return Err(Error::new_spanned(
+                                other,
+                                format!("{name} must be a number"),
+                            ))
+                        }
+                    },
+                    other => {
+                        return Err(Error::new_spanned(
+                            other,
+                            format!("{name} must be a number"),
+                        ))
+                    }
+                };
+                match name {
+                    "year" => year = Some(value),
+                    _ => day = Some(value),
+                }
+            }
+            Some("parser") => parser = Some(pair.value),
+            Some("part1") => parts[0] = Some(pair.value),
+            Some("part2") => parts[1] = Some(pair.value),
+            _ => {
+                return Err(Error::new_spanned(
+                    pair.path,
+                    "expected year, day, parser, part1 or part2",
+                ))
+            }
+        }
+    }
+    Ok(Args {
+        year: year.unwrap_or(DEFAULT_YEAR),
+        day: day.ok_or_else(|| Error::new(Span::call_site(), "missing `day = N`"))?,
+        parser: parser.ok_or_else(|| Error::new(Span::call_site(), "missing `parser = ..`"))?,
+        parts,
+    })
+}
+
+/// Same lookup as `utils::puzzle_dir`, but from the crate being compiled
+fn puzzle_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("AOC_PUZZLE_DIR") {
+        return PathBuf::from(dir);
+    }
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("puzzles"))
+        .find(|candidate| candidate.is_dir())
+        .unwrap_or_else(|| manifest_dir.join("../puzzles"))
+}
+
+fn expand(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    let args = parse_args(input)?;
+    let year_dir = puzzle_dir().join(args.year.to_string());
+    let manifest_path = year_dir.join(format!("day{}_tests.toml", args.day));
+    let cases = fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| read_manifest(&text))
+        .map_err(|e| {
+            Error::new(
+                Span::call_site(),
+                format!("{}: {e}", manifest_path.display()),
+            )
+        })?;
+
+    let parser = &args.parser;
+    let year = args.year;
+    let mut tests = vec![];
+    for case in cases {
+        let Some(solve) = &args.parts[case.part - 1] else {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "{} has a part{} answer but no `part{} = ..`",
+                    case.input, case.part, case.part
+                ),
+            ));
+        };
+        let stem: String = case
+            .input
+            .trim_end_matches(".txt")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let name = format_ident!("{}_part{}", stem, case.part);
+        let (input, part, answer) = (&case.input, case.part, &case.answer);
+        tests.push(quote! {
+            #[test]
+            fn #name() {
+                fn apply<T, R>(data: T, part: impl FnOnce(T) -> R) -> R {
+                    part(data)
+                }
+                let data = ::utils::load_puzzle_file(&::utils::year_dir(#year).join(#input), #parser);
+                let answer = apply(data, #solve);
+                assert_eq!(answer.to_string(), #answer, "{} part {}", #input, #part);
+            }
+        });
+    }
+
+    // Makes cargo rebuild the tests when the manifest changes
+    let manifest_path = manifest_path.to_string_lossy().to_string();
+    Ok(quote! {
+        const _: &str = include_str!(#manifest_path);
+        #(#tests)*
+    })
+}
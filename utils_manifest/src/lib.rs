@@ -0,0 +1,47 @@
+//! Reading a day's example manifest, `day{N}_tests.toml`
+//!
+//! Its own crate so that both `utils` and `utils_macros` can use it, as a
+//! proc-macro crate can't depend on `utils`.
+
+/// One expected answer from a day's example manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleCase {
+    /// The example's file name in the year's puzzle directory
+    pub input: String,
+    pub part: usize,
+    pub answer: String,
+}
+
+/// The expected answers in a manifest, part 1 before part 2 for each example
+///
+/// A fresh day's manifest has no examples yet, that's fine. Answers that aren't
+/// strings are kept as TOML writes them, `7` for `part2 = 7`.
+pub fn read_manifest(text: &str) -> Result<Vec<ExampleCase>, String> {
+    let table: toml::Table = text.parse().map_err(|e| format!("{e}"))?;
+    let examples = match table.get("example") {
+        Some(toml::Value::Array(examples)) => examples.as_slice(),
+        Some(_) => return Err("`example` must be [[example]]".to_string()),
+        None => &[],
+    };
+
+    let mut cases = vec![];
+    for example in examples {
+        let input = example
+            .get("input")
+            .and_then(toml::Value::as_str)
+            .ok_or("[[example]] without an input")?;
+        for part in 1..=2 {
+            let answer = match example.get(format!("part{part}").as_str()) {
+                Some(toml::Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => continue,
+            };
+            cases.push(ExampleCase {
+                input: input.to_string(),
+                part,
+                answer,
+            });
+        }
+    }
+    Ok(cases)
+}